@@ -1,5 +1,11 @@
 use bigdecimal::{num_bigint::BigInt, BigDecimal, FromPrimitive, One, Zero};
 use web_sys::js_sys::Date;
+
+use crate::{
+    birthday::{birthday, Fnv},
+    checksum::Checksum,
+    gf2::{kernel, Crc},
+};
 pub fn powers(base: &BigInt, modulo: &BigInt, length: usize) -> Vec<BigInt> {
     let mut result = Vec::new();
     result.push(BigInt::one() % modulo);
@@ -9,9 +15,244 @@ pub fn powers(base: &BigInt, modulo: &BigInt, length: usize) -> Vec<BigInt> {
     result
 }
 
-fn dot(a: &Vec<BigInt>, b: &Vec<BigInt>) -> BigInt {
+fn dot(a: &[BigInt], b: &[BigInt]) -> BigInt {
     a.iter().zip(b.iter()).map(|(a, b)| a * b).sum::<BigInt>()
 }
+
+fn lcm(a: &BigInt, b: &BigInt) -> BigInt {
+    let (mut x, mut y) = (a.clone(), b.clone());
+    while !y.is_zero() {
+        let r = &x % &y;
+        x = y;
+        y = r;
+    }
+    a / x * b
+}
+
+// Collisions are decoded over every difference of two characters in the
+// alphabet that is a multiple of `gap`, the step with the longest run of
+// consecutive multiples. Modes that choose values rather than differences use
+// the longest arithmetic progression inside the alphabet.
+#[derive(Clone)]
+pub struct Alphabet {
+    chars: Vec<char>,
+    gap: u32,
+    progression: Vec<char>,
+}
+
+impl Alphabet {
+    pub fn new(chars: &str) -> Self {
+        let mut unique = Vec::new();
+        for c in chars.chars() {
+            if !unique.contains(&c) {
+                unique.push(c);
+            }
+        }
+        let mut sorted = unique.iter().map(|&c| c as u32).collect::<Vec<_>>();
+        sorted.sort();
+        let mut differences = Vec::new();
+        for (i, &x) in sorted.iter().enumerate() {
+            for &y in &sorted[i + 1..] {
+                differences.push(y - x);
+            }
+        }
+        differences.sort();
+        differences.dedup();
+        let run = |d: u32| {
+            (1..)
+                .take_while(|k| differences.binary_search(&(k * d)).is_ok())
+                .count()
+        };
+        let gap = differences
+            .iter()
+            .copied()
+            .max_by_key(|&d| (run(d), std::cmp::Reverse(d)))
+            .unwrap_or(1);
+        let mut progression = vec![sorted[0]];
+        for i in 0..sorted.len() {
+            for j in i + 1..sorted.len() {
+                let step = sorted[j] - sorted[i];
+                let mut cur = vec![sorted[i]];
+                while let Ok(k) = sorted.binary_search(&(cur.last().unwrap() + step)) {
+                    cur.push(sorted[k]);
+                }
+                if cur.len() > progression.len() {
+                    progression = cur;
+                }
+            }
+        }
+        Self {
+            chars: sorted.iter().map(|&c| char::from_u32(c).unwrap()).collect(),
+            gap,
+            progression: progression
+                .into_iter()
+                .map(|c| char::from_u32(c).unwrap())
+                .collect(),
+        }
+    }
+    pub fn contains(&self, c: char) -> bool {
+        self.chars.contains(&c)
+    }
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+    fn size(&self) -> usize {
+        self.progression.len()
+    }
+    fn span(&self) -> usize {
+        (0..=(*self.chars.last().unwrap() as u32 - self.chars[0] as u32) / self.gap)
+            .filter(|&k| self.pair(&BigInt::from(k)).is_some())
+            .max()
+            .unwrap() as usize
+            + 1
+    }
+    fn sigma(&self) -> BigInt {
+        BigInt::from_usize(self.span()).unwrap()
+    }
+    fn differences(&self) -> Vec<bool> {
+        (0..self.span())
+            .map(|k| self.pair(&BigInt::from_usize(k).unwrap()).is_some())
+            .collect()
+    }
+    fn first(&self) -> char {
+        self.progression[0]
+    }
+    fn step(&self) -> BigInt {
+        match self.progression[..] {
+            [a, b, ..] => BigInt::from_u32(b as u32 - a as u32).unwrap(),
+            _ => BigInt::one(),
+        }
+    }
+    fn value(&self, x: &BigInt) -> char {
+        self.progression[usize::try_from(x).unwrap()]
+    }
+    fn pair(&self, x: &BigInt) -> Option<(char, char)> {
+        let d = i64::try_from(x).ok()?.checked_mul(self.gap as i64)?;
+        self.chars.iter().find_map(|&c| {
+            let other = u32::try_from(c as i64 + d).ok().and_then(char::from_u32)?;
+            self.contains(other).then_some((c, other))
+        })
+    }
+}
+
+#[derive(Clone)]
+struct Embedding {
+    lo: Vec<BigInt>,
+    hi: Vec<BigInt>,
+    scale: Vec<BigInt>,
+    marker: BigInt,
+    exclude: Option<Vec<BigInt>>,
+}
+
+impl Embedding {
+    fn new(lo: Vec<BigInt>, hi: Vec<BigInt>) -> Self {
+        let width = lo
+            .iter()
+            .zip(hi.iter())
+            .map(|(lo, hi)| hi - lo)
+            .filter(|w| !w.is_zero())
+            .fold(BigInt::one(), |acc, w| lcm(&acc, &w));
+        let scale = lo
+            .iter()
+            .zip(hi.iter())
+            .map(|(lo, hi)| {
+                let w = hi - lo;
+                if w.is_zero() {
+                    width.clone()
+                } else {
+                    &width / w
+                }
+            })
+            .collect();
+        Self {
+            lo,
+            hi,
+            scale,
+            marker: width,
+            exclude: None,
+        }
+    }
+    fn lattice(
+        &self,
+        coefficients: &[Vec<BigInt>],
+        target: &[BigInt],
+        modulo: &[BigInt],
+        lambda: &BigInt,
+    ) -> Vec<Vec<BigInt>> {
+        let n = modulo.len();
+        let m = self.lo.len();
+        let mut b = lattice(coefficients, modulo, lambda);
+        for (j, row) in b.iter_mut().enumerate() {
+            if j < m {
+                row[n + j] = &self.scale[j] * 2;
+            }
+            row.push(BigInt::ZERO);
+        }
+        let mut t = vec![BigInt::ZERO; n + m + 1];
+        for i in 0..n {
+            t[i] = -&target[i] * lambda;
+        }
+        for j in 0..m {
+            t[n + j] = -(&self.lo[j] + &self.hi[j]) * &self.scale[j];
+        }
+        t[n + m] = self.marker.clone();
+        b.insert(0, t);
+        b
+    }
+    fn decode(&self, row: &[BigInt]) -> Option<Vec<BigInt>> {
+        let m = self.lo.len();
+        let sign = if row[m] == self.marker {
+            BigInt::one()
+        } else if row[m] == -&self.marker {
+            -BigInt::one()
+        } else {
+            return None;
+        };
+        let mut x = Vec::new();
+        for (((v, scale), lo), hi) in row[..m]
+            .iter()
+            .zip(self.scale.iter())
+            .zip(self.lo.iter())
+            .zip(self.hi.iter())
+        {
+            let v = v * &sign;
+            if !(&v % scale).is_zero() {
+                return None;
+            }
+            let twice = v / scale + lo + hi;
+            if !(&twice % 2u32).is_zero() {
+                return None;
+            }
+            let val: BigInt = twice / 2u32;
+            if &val < lo || &val > hi {
+                return None;
+            }
+            x.push(val);
+        }
+        if self.exclude.as_ref() == Some(&x) {
+            return None;
+        }
+        Some(x)
+    }
+    fn combine(&self, row: &[BigInt], other: &[BigInt]) -> Option<Vec<BigInt>> {
+        let m = self.lo.len();
+        if row[m].is_zero() == other[m].is_zero() {
+            return None;
+        }
+        for sign in [1, -1] {
+            let sum = row
+                .iter()
+                .zip(other.iter())
+                .map(|(a, b)| a + b * sign)
+                .collect::<Vec<_>>();
+            if let Some(x) = self.decode(&sum) {
+                return Some(x);
+            }
+        }
+        None
+    }
+}
+
 struct L2 {
     b: Vec<Vec<BigInt>>,
     r: Vec<Vec<BigDecimal>>,
@@ -20,7 +261,9 @@ struct L2 {
     delta: BigDecimal,
     eta: BigDecimal,
     precision: u64,
-    sigma: usize,
+    sigma: BigInt,
+    differences: Option<Vec<bool>>,
+    embedding: Option<Embedding>,
     timeout: f64,
     start_time: f64,
     n_: usize,
@@ -32,7 +275,7 @@ impl L2 {
         delta: BigDecimal,
         eta: BigDecimal,
         precision: u64,
-        sigma: usize,
+        sigma: BigInt,
         timeout: f64,
         n_: usize,
     ) -> Self {
@@ -49,6 +292,8 @@ impl L2 {
             eta,
             precision,
             sigma,
+            differences: None,
+            embedding: None,
             timeout,
             start_time,
             n_,
@@ -140,10 +385,24 @@ impl L2 {
         if self.b[i][..self.n_].iter().any(|val| val != &BigInt::ZERO) {
             return false;
         }
-        if self.row_max(i) >= BigInt::from_usize(self.sigma).unwrap() {
-            return false;
+        if let Some(embedding) = &self.embedding {
+            let row = &self.b[i][self.n_..];
+            return embedding.decode(row).is_some()
+                || self.b[..i].iter().any(|other| {
+                    other[..self.n_].iter().all(|val| val.is_zero())
+                        && embedding.combine(row, &other[self.n_..]).is_some()
+                });
+        }
+        self.row_max(i) < self.sigma && self.representable(i)
+    }
+    fn representable(&self, i: usize) -> bool {
+        match &self.differences {
+            Some(differences) => self.b[i][self.n_..].iter().all(|val| {
+                i64::try_from(val)
+                    .is_ok_and(|val| differences.get(val.unsigned_abs() as usize) == Some(&true))
+            }),
+            None => true,
         }
-        true
     }
     fn runtime(&self) -> f64 {
         Date::now() / 1000. - self.start_time
@@ -151,6 +410,64 @@ impl L2 {
     fn check_time_out(&self) -> bool {
         self.runtime() > self.timeout
     }
+    fn row_maxes(&self) -> Vec<BigInt> {
+        let mut result = (0..self.b.len())
+            .filter(|&i| self.b[i][..self.n_].iter().all(|val| val.is_zero()))
+            .map(|i| self.row_max(i))
+            .collect::<Vec<_>>();
+        result.sort();
+        result
+    }
+    fn solution(&self) -> Result<Vec<BigInt>, AntiResult> {
+        let n = self.n_;
+        let mut best = None;
+        let mut best_vec = None;
+        for (i, row) in self.b.iter().enumerate() {
+            if row[..n].iter().any(|val| val != &BigInt::ZERO) {
+                continue;
+            }
+            let cur = self.row_max(i);
+            if cur < self.sigma && self.representable(i) {
+                return Ok(row[n..].to_vec());
+            }
+            if best.is_none() || best.clone().unwrap() > cur {
+                best = Some(cur);
+                best_vec = Some(row[n..].to_vec());
+            }
+        }
+        if self.check_time_out() {
+            Err(AntiResult::TimeOut(best_vec))
+        } else {
+            Err(AntiResult::NotFound(self.runtime(), best_vec))
+        }
+    }
+    fn closest(&self) -> Result<Vec<BigInt>, AntiResult> {
+        let n = self.n_;
+        let embedding = self.embedding.as_ref().unwrap();
+        let rows = self
+            .b
+            .iter()
+            .filter(|row| row[..n].iter().all(|val| val.is_zero()))
+            .map(|row| &row[n..])
+            .collect::<Vec<_>>();
+        for row in &rows {
+            if let Some(x) = embedding.decode(row) {
+                return Ok(x);
+            }
+        }
+        for (i, row) in rows.iter().enumerate() {
+            for other in &rows[..i] {
+                if let Some(x) = embedding.combine(row, other) {
+                    return Ok(x);
+                }
+            }
+        }
+        if self.check_time_out() {
+            Err(AntiResult::TimeOut(None))
+        } else {
+            Err(AntiResult::NotFound(self.runtime(), None))
+        }
+    }
     #[allow(unused)]
     fn show_b(&self, k: usize) {
         let n = self.b.len();
@@ -164,8 +481,10 @@ impl L2 {
     }
 }
 
+#[derive(Clone)]
 pub struct Parameters {
     pub length: usize,
+    pub length_b: Option<usize>,
     pub modulo: Vec<BigInt>,
     pub base: Vec<BigInt>,
 
@@ -174,7 +493,29 @@ pub struct Parameters {
     pub eta: BigDecimal,
     pub precision: u64,
     pub palindrome: bool,
-    pub sigma: usize,
+    pub palindromic: bool,
+    pub nested: bool,
+    pub alphabet: Alphabet,
+    pub count: usize,
+    pub target: Option<String>,
+    pub hashes: Option<Vec<BigInt>>,
+    pub substring: bool,
+    pub range: Option<(BigInt, BigInt)>,
+    pub template: Option<String>,
+    pub anagram: bool,
+    pub brackets: bool,
+    pub permutation: bool,
+    pub sparsity: Option<usize>,
+    pub report: bool,
+    pub grid: Option<(usize, usize)>,
+    pub column_base: Vec<BigInt>,
+    pub weights: Option<Vec<Vec<BigInt>>>,
+    pub crc: Option<Crc>,
+    pub checksum: Option<Checksum>,
+    pub fnv: Option<Fnv>,
+    pub zobrist: Option<Vec<u64>>,
+    pub multiset: Option<Vec<Vec<BigInt>>>,
+    pub multiset_count: usize,
 
     pub timeout: f64,
 }
@@ -183,33 +524,112 @@ pub enum AntiResult {
     NotFound(f64, Option<Vec<BigInt>>),
     TimeOut(Option<Vec<BigInt>>),
     Ok(f64, String, String),
+    Multi(f64, Vec<String>, Vec<BigInt>),
+    Preimage(f64, String, Vec<BigInt>),
+    Substring(f64, String, usize, usize, usize),
+    Array(f64, Vec<BigInt>, Vec<BigInt>),
+    Report(f64, Vec<BigInt>),
+    Grid(f64, Vec<String>, Vec<String>),
     Unknown,
 }
 
-fn check(a: &String, b: &String, modulo: Vec<BigInt>, base: Vec<BigInt>) -> bool {
-    let n = modulo.len();
-    let length = 0;
-    let a = a
+fn hash_values(values: &[BigInt], modulo: &BigInt, base: &BigInt) -> BigInt {
+    (dot(&powers(base, modulo, values.len()), values) % modulo + modulo) % modulo
+}
+
+fn hash(s: &str, modulo: &BigInt, base: &BigInt) -> BigInt {
+    let s = s
         .chars()
-        .map(|c| BigInt::from_u8(c as u8).unwrap())
+        .map(|c| BigInt::from_u32(c as u32).unwrap())
         .collect::<Vec<_>>();
-    let b = b
+    hash_values(&s, modulo, base)
+}
+
+fn weighted_hash(s: &str, modulo: &BigInt, weights: &[BigInt]) -> BigInt {
+    let s = s
         .chars()
-        .map(|c| BigInt::from_u8(c as u8).unwrap())
+        .map(|c| BigInt::from_u32(c as u32).unwrap())
         .collect::<Vec<_>>();
+    (dot(weights, &s) % modulo + modulo) % modulo
+}
+
+fn check(a: &str, b: &str, modulo: &[BigInt], base: &[BigInt]) -> bool {
+    if a.chars().count() != b.chars().count() {
+        return false;
+    }
+    modulo
+        .iter()
+        .zip(base.iter())
+        .all(|(modulo, base)| hash(a, modulo, base) == hash(b, modulo, base))
+}
+
+fn grid_hash(grid: &[String], modulo: &BigInt, row_base: &BigInt, column_base: &BigInt) -> BigInt {
+    let rows = grid
+        .iter()
+        .map(|s| hash(s, modulo, column_base))
+        .collect::<Vec<_>>();
+    hash_values(&rows, modulo, row_base)
+}
+
+fn prefix_hashes(s: &str, modulo: &BigInt, base: &BigInt) -> Vec<BigInt> {
+    let mut result = vec![BigInt::ZERO];
+    for c in s.chars() {
+        let next = (result.last().unwrap() * base + c as u32) % modulo;
+        result.push(next);
+    }
+    result
+}
+
+fn substring_hash(
+    prefix: &[BigInt],
+    pow: &[BigInt],
+    modulo: &BigInt,
+    l: usize,
+    r: usize,
+) -> BigInt {
+    ((&prefix[r] - &prefix[l] * &pow[r - l]) % modulo + modulo) % modulo
+}
+
+fn lattice(coefficients: &[Vec<BigInt>], modulo: &[BigInt], lambda: &BigInt) -> Vec<Vec<BigInt>> {
+    let n = modulo.len();
+    let m = coefficients[0].len();
+    let mut b = vec![vec![BigInt::ZERO; m + n]; m + n];
     for i in 0..n {
-        let pow = powers(&base[i], &modulo[i], length);
-        let mut ha = BigInt::ZERO;
-        let mut hb = BigInt::ZERO;
-        for j in 0..length {
-            ha += &pow[j] * &a[j];
-            hb += &pow[j] * &b[j];
-        }
-        if ha != hb {
-            return false;
+        for j in 0..m {
+            b[j][i] = (&coefficients[i][j] % &modulo[i] + &modulo[i]) % &modulo[i] * lambda;
         }
+        b[m + i][i] = &modulo[i] * lambda;
+    }
+    for j in 0..m {
+        b[j][n + j] = BigInt::one();
     }
-    true
+    b
+}
+
+fn shifted(
+    alphabet: &Alphabet,
+    hashes: &[BigInt],
+    length: usize,
+    modulo: &[BigInt],
+    base: &[BigInt],
+) -> (Vec<Vec<BigInt>>, Vec<BigInt>) {
+    let offset = alphabet.first().to_string().repeat(length);
+    let coefficients = (0..modulo.len())
+        .map(|i| {
+            powers(&base[i], &modulo[i], length)
+                .into_iter()
+                .map(|val| val * alphabet.step() % &modulo[i])
+                .collect()
+        })
+        .collect();
+    let target = (0..modulo.len())
+        .map(|i| (&hashes[i] - hash(&offset, &modulo[i], &base[i])) % &modulo[i])
+        .collect();
+    (coefficients, target)
+}
+
+fn decode(diff: &[BigInt], alphabet: &Alphabet) -> (String, String) {
+    diff.iter().map(|d| alphabet.pair(d).unwrap()).unzip()
 }
 
 pub fn anti_palindrome_hash(parameters: Parameters) -> AntiResult {
@@ -222,91 +642,236 @@ pub fn anti_palindrome_hash(parameters: Parameters) -> AntiResult {
         eta,
         precision,
         timeout,
-        palindrome: _,
-        sigma,
+        alphabet,
+        ..
     } = parameters;
     if length == 1 {
         return AntiResult::NotFound(0., None);
     }
     let n = modulo.len();
-    let mut b = vec![vec![BigInt::ZERO; length / 2 + n]; length / 2 + n];
+    let mut coefficients = vec![vec![BigInt::ZERO; length / 2]; n];
     for i in 0..n {
         powers(&base[i], &modulo[i], length)
             .into_iter()
             .enumerate()
             .for_each(|(j, val)| {
                 if j < length / 2 {
-                    b[j][i] += val * &lambda
+                    coefficients[i][j] += val
                 } else if length - 1 - j < length / 2 {
-                    b[length - 1 - j][i] -= val * &lambda
+                    coefficients[i][length - 1 - j] -= val
                 }
             });
-        b[length / 2 + i][i] = &modulo[i] * &lambda;
     }
-    for i in 0..length / 2 {
-        b[i][n + i] = BigInt::one();
+    let b = lattice(&coefficients, &modulo, &lambda);
+    let mut l2 = L2::new(b, delta, eta, precision, alphabet.sigma(), timeout, n);
+    l2.differences = Some(alphabet.differences());
+    l2.reduce();
+    let row = match l2.solution() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let (mut a, mut b) = decode(&row, &alphabet);
+    let pa = a.chars().rev().collect::<String>();
+    let pb = b.chars().rev().collect::<String>();
+    if length % 2 == 1 {
+        a.push(alphabet.first());
+        b.push(alphabet.first());
+    }
+    a += &pb;
+    b += &pa;
+    if !check(&a, &b, &modulo, &base) {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Ok(l2.runtime(), a, b)
+}
+
+pub fn anti_palindromic_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        length,
+        modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        alphabet,
+        ..
+    } = parameters;
+    let half = length.div_ceil(2);
+    let n = modulo.len();
+    let mut coefficients = vec![vec![BigInt::ZERO; half]; n];
+    for i in 0..n {
+        powers(&base[i], &modulo[i], length)
+            .into_iter()
+            .enumerate()
+            .for_each(|(j, val)| coefficients[i][j.min(length - 1 - j)] += val);
     }
-    let mut l2 = L2::new(b, delta, eta, precision, sigma, timeout, n);
+    let b = lattice(&coefficients, &modulo, &lambda);
+    let mut l2 = L2::new(b, delta, eta, precision, alphabet.sigma(), timeout, n);
+    l2.differences = Some(alphabet.differences());
     l2.reduce();
-    let mut best = None;
-    let mut best_vec = None;
-    for row in &l2.b {
-        if row[..n].iter().any(|val| val != &BigInt::ZERO) {
-            continue;
-        }
-        let cur = row[n..]
-            .iter()
+    let row = match l2.solution() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let (x, y) = decode(&row, &alphabet);
+    let mirror = |s: String| {
+        let rev = s.chars().rev().skip(length % 2).collect::<String>();
+        s + &rev
+    };
+    let (a, b) = (mirror(x), mirror(y));
+    if !check(&a, &b, &modulo, &base) {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Ok(l2.runtime(), a, b)
+}
+
+pub fn anti_nested_hash(parameters: Parameters) -> AntiResult {
+    let start_time = Date::now() / 1000.;
+    let Parameters {
+        length,
+        modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        ..
+    } = parameters.clone();
+    let (mut a, mut b) = match anti_hash(Parameters {
+        modulo: modulo[..1].to_vec(),
+        base: base[..1].to_vec(),
+        nested: false,
+        ..parameters
+    }) {
+        AntiResult::Ok(_, a, b) => (a, b),
+        result => return result,
+    };
+    for i in 1..modulo.len() {
+        let runtime = Date::now() / 1000. - start_time;
+        let block = a.chars().count();
+        let step = powers(&base[i], &modulo[i], block + 1).pop().unwrap();
+        let coefficients = vec![powers(&step, &modulo[i], length)
+            .into_iter()
             .map(|val| {
-                if val >= &BigInt::ZERO {
-                    val.clone()
-                } else {
-                    -val
-                }
+                val * (hash(&b, &modulo[i], &base[i]) - hash(&a, &modulo[i], &base[i])) % &modulo[i]
             })
-            .max()
-            .unwrap();
-        if cur < BigInt::from_usize(sigma).unwrap() {
-            let mut a = String::new();
-            let mut b = String::new();
-            for i in 0..length / 2 {
-                let diff: i32 = row[n + i].clone().try_into().unwrap();
-                if diff >= 0 {
-                    a.push('a');
-                    b.push(('a' as u8 + diff as u8) as char);
-                } else {
-                    a.push(('a' as u8 + (-diff) as u8) as char);
-                    b.push('a');
-                }
-            }
-            let pa = a.clone().chars().into_iter().rev().collect::<String>();
-            let pb = b.clone().chars().into_iter().rev().collect::<String>();
-            if length % 2 == 1 {
-                a.push('a');
-                b.push('a');
-            }
-            a += &pb;
-            b += &pa;
-            if !check(&a, &b, modulo, base) {
-                return AntiResult::Unknown;
+            .collect()];
+        let lb = lattice(&coefficients, &modulo[i..i + 1], &lambda);
+        let mut l2 = L2::new(
+            lb,
+            delta.clone(),
+            eta.clone(),
+            precision,
+            BigInt::from(2),
+            timeout - runtime,
+            1,
+        );
+        l2.reduce();
+        let row = match l2.solution() {
+            Ok(row) => row,
+            Err(AntiResult::NotFound(_, best)) => {
+                return AntiResult::NotFound(Date::now() / 1000. - start_time, best)
             }
-            return AntiResult::Ok(l2.runtime(), a, b);
-        }
-        if best.is_none() || best.clone().unwrap() > cur {
-            best = Some(cur);
-            best_vec = Some(row[n..].iter().map(|val| val.clone()).collect());
-        }
+            Err(result) => return result,
+        };
+        let (x, y) = decode(&row, &Alphabet::new("ab"));
+        let block = |c| if c == 'a' { a.as_str() } else { b.as_str() };
+        (a, b) = (
+            x.chars().map(block).collect(),
+            y.chars().map(block).collect(),
+        );
     }
-    if l2.check_time_out() {
-        return AntiResult::TimeOut(best_vec);
-    } else {
-        AntiResult::NotFound(l2.runtime(), best_vec)
+    if !check(&a, &b, &modulo, &base) {
+        return AntiResult::Unknown;
     }
+    AntiResult::Ok(Date::now() / 1000. - start_time, a, b)
 }
 
-pub fn anti_hash(parameters: Parameters) -> AntiResult {
-    if parameters.palindrome {
-        return anti_palindrome_hash(parameters);
+pub fn anti_second_preimage_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        alphabet,
+        target,
+        ..
+    } = parameters;
+    let s = target.unwrap();
+    let length = s.chars().count();
+    let n = modulo.len();
+    // Each position moves along the longest evenly spaced run of the
+    // alphabet through its own character, so every decoded offset is valid.
+    let runs = s
+        .chars()
+        .map(|c| {
+            let shift = |step: i64, k: i64| {
+                u32::try_from(c as i64 + k * step)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .filter(|&other| alphabet.contains(other))
+            };
+            let reach = |step: i64, sign: i64| {
+                (1..)
+                    .take_while(|&k| shift(step, sign * k).is_some())
+                    .count() as i64
+            };
+            alphabet
+                .chars
+                .iter()
+                .filter(|&&other| other != c)
+                .map(|&other| (other as i64 - c as i64).abs())
+                .map(|step| (step, -reach(step, -1), reach(step, 1)))
+                .max_by_key(|&(step, lo, hi)| (hi - lo, std::cmp::Reverse(step)))
+                .unwrap_or((1, 0, 0))
+        })
+        .collect::<Vec<_>>();
+    let (lo, hi): (Vec<_>, Vec<_>) = runs
+        .iter()
+        .map(|&(_, lo, hi)| (BigInt::from(lo), BigInt::from(hi)))
+        .unzip();
+    if lo == hi {
+        return AntiResult::NotFound(0., None);
     }
+    let coefficients = (0..n)
+        .map(|i| {
+            powers(&base[i], &modulo[i], length)
+                .into_iter()
+                .zip(runs.iter())
+                .map(|(val, &(step, _, _))| val * step % &modulo[i])
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    let mut embedding = Embedding::new(lo, hi);
+    embedding.exclude = Some(vec![BigInt::ZERO; length]);
+    let b = embedding.lattice(&coefficients, &vec![BigInt::ZERO; n], &modulo, &lambda);
+    let mut l2 = L2::new(b, delta, eta, precision, alphabet.sigma(), timeout, n);
+    l2.embedding = Some(embedding);
+    l2.reduce();
+    let row = match l2.closest() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let t = s
+        .chars()
+        .zip(row.iter().zip(runs.iter()))
+        .map(|(c, (d, &(step, _, _)))| {
+            char::from_u32((c as i64 + i64::try_from(d).unwrap() * step) as u32).unwrap()
+        })
+        .collect::<String>();
+    if t == s || !check(&s, &t, &modulo, &base) {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Ok(l2.runtime(), s, t)
+}
+
+pub fn anti_preimage_hash(parameters: Parameters) -> AntiResult {
     let Parameters {
         length,
         modulo,
@@ -316,69 +881,939 @@ pub fn anti_hash(parameters: Parameters) -> AntiResult {
         eta,
         precision,
         timeout,
-        palindrome: _,
-        sigma,
+        alphabet,
+        hashes,
+        ..
     } = parameters;
+    let hashes = hashes.unwrap();
     let n = modulo.len();
-    let mut b = vec![vec![BigInt::ZERO; length + n]; length + n];
+    let (coefficients, target) = shifted(&alphabet, &hashes, length, &modulo, &base);
+    let embedding = Embedding::new(
+        vec![BigInt::ZERO; length],
+        vec![BigInt::from_usize(alphabet.size() - 1).unwrap(); length],
+    );
+    let b = embedding.lattice(&coefficients, &target, &modulo, &lambda);
+    let mut l2 = L2::new(b, delta, eta, precision, alphabet.sigma(), timeout, n);
+    l2.embedding = Some(embedding);
+    l2.reduce();
+    let row = match l2.closest() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let s = row.iter().map(|x| alphabet.value(x)).collect::<String>();
+    if (0..n).any(|i| hash(&s, &modulo[i], &base[i]) != hashes[i]) {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Preimage(l2.runtime(), s, hashes)
+}
+
+pub fn anti_substring_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        length,
+        modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        alphabet,
+        ..
+    } = parameters;
+    let n = modulo.len();
+    let coefficients = (0..n)
+        .map(|i| {
+            let mut pow = powers(&base[i], &modulo[i], length);
+            pow.reverse();
+            pow
+        })
+        .collect::<Vec<_>>();
+    let b = lattice(&coefficients, &modulo, &lambda);
+    let mut l2 = L2::new(b, delta, eta, precision, alphabet.sigma(), timeout, n);
+    l2.differences = Some(alphabet.differences());
+    l2.reduce();
+    let row = match l2.solution() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let (a, b) = decode(&row, &alphabet);
+    let s = a + &b;
     for i in 0..n {
-        powers(&base[i], &modulo[i], length)
-            .into_iter()
+        let prefix = prefix_hashes(&s, &modulo[i], &base[i]);
+        let pow = powers(&base[i], &modulo[i], length + 1);
+        if substring_hash(&prefix, &pow, &modulo[i], 0, length)
+            != substring_hash(&prefix, &pow, &modulo[i], length, 2 * length)
+        {
+            return AntiResult::Unknown;
+        }
+    }
+    AntiResult::Substring(l2.runtime(), s, 0, length, length)
+}
+
+pub fn anti_array_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        length,
+        modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        range,
+        ..
+    } = parameters;
+    let (lo, hi) = range.unwrap();
+    let n = modulo.len();
+    let coefficients = (0..n)
+        .map(|i| powers(&base[i], &modulo[i], length))
+        .collect::<Vec<_>>();
+    let b = lattice(&coefficients, &modulo, &lambda);
+    let mut l2 = L2::new(b, delta, eta, precision, &hi - &lo + 1, timeout, n);
+    l2.reduce();
+    let row = match l2.solution() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let (a, b): (Vec<_>, Vec<_>) = row
+        .iter()
+        .map(|d| {
+            if d >= &BigInt::ZERO {
+                (lo.clone(), &lo + d)
+            } else {
+                (&lo - d, lo.clone())
+            }
+        })
+        .unzip();
+    if (0..n)
+        .any(|i| hash_values(&a, &modulo[i], &base[i]) != hash_values(&b, &modulo[i], &base[i]))
+    {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Array(l2.runtime(), a, b)
+}
+
+pub fn anti_template_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        alphabet,
+        template,
+        ..
+    } = parameters;
+    let template = template.unwrap();
+    let length = template.chars().count();
+    let free = template
+        .chars()
+        .enumerate()
+        .filter(|&(_, c)| c == '?')
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    if free.is_empty() {
+        return AntiResult::NotFound(0., None);
+    }
+    let n = modulo.len();
+    let coefficients = (0..n)
+        .map(|i| {
+            let pow = powers(&base[i], &modulo[i], length);
+            free.iter().map(|&j| pow[j].clone()).collect()
+        })
+        .collect::<Vec<_>>();
+    let b = lattice(&coefficients, &modulo, &lambda);
+    let mut l2 = L2::new(b, delta, eta, precision, alphabet.sigma(), timeout, n);
+    l2.differences = Some(alphabet.differences());
+    l2.reduce();
+    let row = match l2.solution() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let (x, y) = decode(&row, &alphabet);
+    let fill = |s: String| {
+        let mut s = s.chars();
+        template
+            .chars()
+            .map(|c| if c == '?' { s.next().unwrap() } else { c })
+            .collect::<String>()
+    };
+    let (a, b) = (fill(x), fill(y));
+    if !check(&a, &b, &modulo, &base) {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Ok(l2.runtime(), a, b)
+}
+
+pub fn anti_unequal_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        length,
+        length_b,
+        modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        alphabet,
+        ..
+    } = parameters;
+    let length_b = length_b.unwrap();
+    let (short, long) = (length.min(length_b), length.max(length_b));
+    let n = modulo.len();
+    let coefficients = (0..n)
+        .map(|i| {
+            powers(&base[i], &modulo[i], long)
+                .into_iter()
+                .map(|val| val * alphabet.step() % &modulo[i])
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    let first = BigInt::from_u32(alphabet.first() as u32).unwrap();
+    let target = (0..n)
+        .map(|i| {
+            -&first
+                * powers(&base[i], &modulo[i], long)[short..]
+                    .iter()
+                    .sum::<BigInt>()
+                % &modulo[i]
+        })
+        .collect::<Vec<_>>();
+    let sigma = BigInt::from_usize(alphabet.size() - 1).unwrap();
+    let lo = (0..long)
+        .map(|i| if i < short { -&sigma } else { BigInt::ZERO })
+        .collect();
+    let embedding = Embedding::new(lo, vec![sigma; long]);
+    let b = embedding.lattice(&coefficients, &target, &modulo, &lambda);
+    let mut l2 = L2::new(b, delta, eta, precision, alphabet.sigma(), timeout, n);
+    l2.embedding = Some(embedding);
+    l2.reduce();
+    let row = match l2.closest() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let (a, mut b): (String, String) = row[..short]
+        .iter()
+        .map(|x| {
+            (
+                alphabet.value(&(-x).max(BigInt::ZERO)),
+                alphabet.value(&x.clone().max(BigInt::ZERO)),
+            )
+        })
+        .unzip();
+    b.extend(row[short..].iter().map(|x| alphabet.value(x)));
+    let (a, b) = if length > length_b { (b, a) } else { (a, b) };
+    if !modulo
+        .iter()
+        .zip(base.iter())
+        .all(|(modulo, base)| hash(&a, modulo, base) == hash(&b, modulo, base))
+    {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Ok(l2.runtime(), a, b)
+}
+
+pub fn anti_grid_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        alphabet,
+        grid,
+        column_base,
+        ..
+    } = parameters;
+    let (rows, columns) = grid.unwrap();
+    let n = modulo.len();
+    let coefficients = (0..n)
+        .map(|i| {
+            let p = powers(&base[i], &modulo[i], rows);
+            let q = powers(&column_base[i], &modulo[i], columns);
+            p.iter()
+                .flat_map(|x| q.iter().map(move |y| x * y))
+                .map(|val| val % &modulo[i])
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    let b = lattice(&coefficients, &modulo, &lambda);
+    let mut l2 = L2::new(b, delta, eta, precision, alphabet.sigma(), timeout, n);
+    l2.differences = Some(alphabet.differences());
+    l2.reduce();
+    let row = match l2.solution() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let (a, b): (Vec<_>, Vec<_>) = row
+        .chunks(columns)
+        .map(|chunk| decode(chunk, &alphabet))
+        .unzip();
+    if a == b
+        || (0..n).any(|i| {
+            grid_hash(&a, &modulo[i], &base[i], &column_base[i])
+                != grid_hash(&b, &modulo[i], &base[i], &column_base[i])
+        })
+    {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Grid(l2.runtime(), a, b)
+}
+
+pub fn anti_checksum_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        length,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        alphabet,
+        checksum,
+        ..
+    } = parameters;
+    let checksum = checksum.unwrap();
+    let modulo = checksum.moduli();
+    let coefficients = checksum.weights(length);
+    let b = lattice(&coefficients, &modulo, &lambda);
+    let mut l2 = L2::new(
+        b,
+        delta,
+        eta,
+        precision,
+        alphabet.sigma(),
+        timeout,
+        modulo.len(),
+    );
+    l2.differences = Some(alphabet.differences());
+    l2.reduce();
+    let row = match l2.solution() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let (a, b) = decode(&row, &alphabet);
+    if a == b || checksum.checksum(a.as_bytes()) != checksum.checksum(b.as_bytes()) {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Ok(l2.runtime(), a, b)
+}
+
+pub fn anti_birthday_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        length,
+        alphabet,
+        fnv,
+        timeout,
+        ..
+    } = parameters;
+    birthday(&fnv.unwrap(), &alphabet, length, timeout)
+}
+
+pub fn anti_crc_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        length,
+        alphabet,
+        crc,
+        ..
+    } = parameters;
+    let crc = crc.unwrap();
+    let start_time = Date::now() / 1000.;
+    let first = alphabet.first();
+    let other = match alphabet
+        .chars
+        .iter()
+        .find(|&&c| c != first && c.len_utf8() == first.len_utf8())
+    {
+        Some(&c) => c,
+        None => return AntiResult::NotFound(0., None),
+    };
+    let a = first.to_string().repeat(length);
+    let flip = |positions: &[usize]| {
+        a.chars()
             .enumerate()
-            .for_each(|(j, val)| b[j][i] = val * &lambda);
-        b[length + i][i] = &modulo[i] * &lambda;
+            .map(|(i, c)| if positions.contains(&i) { other } else { c })
+            .collect::<String>()
+    };
+    let h = crc.checksum(a.as_bytes());
+    let vectors = (0..length.min(crc.width as usize + 1))
+        .map(|i| crc.checksum(flip(&[i]).as_bytes()) ^ h)
+        .collect::<Vec<_>>();
+    let positions = match kernel(&vectors) {
+        Some(positions) => positions,
+        None => return AntiResult::NotFound(Date::now() / 1000. - start_time, None),
+    };
+    let b = flip(&positions);
+    if a == b || crc.checksum(a.as_bytes()) != crc.checksum(b.as_bytes()) {
+        return AntiResult::Unknown;
     }
-    for i in 0..length {
-        b[i][n + i] = BigInt::one();
+    AntiResult::Ok(Date::now() / 1000. - start_time, a, b)
+}
+
+pub fn anti_zobrist_hash(parameters: Parameters) -> AntiResult {
+    let Parameters { zobrist, .. } = parameters;
+    let table = zobrist.unwrap();
+    let start_time = Date::now() / 1000.;
+    let subset = match kernel(&table) {
+        Some(subset) => subset,
+        None => return AntiResult::NotFound(Date::now() / 1000. - start_time, None),
+    };
+    let (a, b) = subset.split_at(subset.len() / 2);
+    let xor = |indices: &[usize]| indices.iter().fold(0, |acc, &i| acc ^ table[i]);
+    if xor(a) != xor(b) {
+        return AntiResult::Unknown;
     }
-    let mut l2 = L2::new(b, delta, eta, precision, sigma, timeout, n);
+    let to_vec = |indices: &[usize]| {
+        indices
+            .iter()
+            .map(|&i| BigInt::from_usize(i).unwrap())
+            .collect()
+    };
+    AntiResult::Array(Date::now() / 1000. - start_time, to_vec(a), to_vec(b))
+}
+
+pub fn anti_multiset_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        modulo,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        multiset,
+        multiset_count,
+        ..
+    } = parameters;
+    let table = multiset.unwrap();
+    let n = modulo.len();
+    let b = lattice(&table, &modulo, &lambda);
+    let count = BigInt::from_usize(multiset_count).unwrap();
+    let mut l2 = L2::new(b, delta, eta, precision, count, timeout, n);
     l2.reduce();
-    let mut best = None;
-    let mut best_vec = None;
-    for row in &l2.b {
-        if row[..n].iter().any(|val| val != &BigInt::ZERO) {
-            continue;
+    let row = match l2.solution() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let elements = |sign: i32| {
+        row.iter()
+            .enumerate()
+            .flat_map(|(x, d)| {
+                let count = (d * sign).max(BigInt::ZERO);
+                std::iter::repeat_n(BigInt::from_usize(x).unwrap(), count.try_into().unwrap())
+            })
+            .collect::<Vec<_>>()
+    };
+    let (a, b) = (elements(-1), elements(1));
+    let sum = |elements: &[BigInt], i: usize| {
+        elements
+            .iter()
+            .map(|x| &table[i][usize::try_from(x).unwrap()])
+            .sum::<BigInt>()
+            % &modulo[i]
+    };
+    if (0..n).any(|i| sum(&a, i) != sum(&b, i)) {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Array(l2.runtime(), a, b)
+}
+
+pub fn anti_anagram_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        length,
+        mut modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        alphabet,
+        ..
+    } = parameters;
+    let n = modulo.len();
+    let mut coefficients = (0..n)
+        .map(|i| powers(&base[i], &modulo[i], length))
+        .collect::<Vec<_>>();
+    coefficients.push(vec![BigInt::one(); length]);
+    modulo.push(BigInt::from_usize(length * alphabet.size() + 1).unwrap());
+    let b = lattice(&coefficients, &modulo, &lambda);
+    let bound = BigInt::from_usize(alphabet.size() / 2 + 1).unwrap();
+    let mut l2 = L2::new(b, delta, eta, precision, bound, timeout, n + 1);
+    l2.reduce();
+    let row = match l2.solution() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let mut order = Vec::new();
+    let (mut positive, mut negative): (Vec<_>, Vec<_>) =
+        (0..length).partition(|&i| row[i] >= BigInt::ZERO);
+    let mut sum = BigInt::ZERO;
+    while !positive.is_empty() || !negative.is_empty() {
+        let i = if (sum <= BigInt::ZERO && !positive.is_empty()) || negative.is_empty() {
+            positive.pop().unwrap()
+        } else {
+            negative.pop().unwrap()
+        };
+        order.push((i, sum.clone()));
+        sum += &row[i];
+    }
+    let offset = -order.iter().map(|(_, val)| val).min().unwrap();
+    let mut x = vec![BigInt::ZERO; length];
+    for &(i, ref val) in &order {
+        x[i] = val + &offset;
+    }
+    let a = x.iter().map(|val| alphabet.value(val)).collect::<String>();
+    let b = x
+        .iter()
+        .zip(row.iter())
+        .map(|(val, d)| alphabet.value(&(val + d)))
+        .collect::<String>();
+    let (mut x, mut y) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+    x.sort();
+    y.sort();
+    if x != y || !check(&a, &b, &modulo[..n], &base) {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Ok(l2.runtime(), a, b)
+}
+
+fn balance(s: &str) -> (i64, i64) {
+    let mut cur = 0;
+    let mut min = 0;
+    for c in s.chars() {
+        cur += if c == '(' { 1 } else { -1 };
+        min = min.min(cur);
+    }
+    (cur, min)
+}
+
+pub fn anti_bracket_hash(parameters: Parameters) -> AntiResult {
+    let start_time = Date::now() / 1000.;
+    let Parameters {
+        length,
+        modulo,
+        base,
+        timeout,
+        ..
+    } = parameters.clone();
+    if length % 2 != 0 {
+        return AntiResult::NotFound(0., None);
+    }
+    let mut pad = 0;
+    let (a, b) = loop {
+        if 2 * pad >= length {
+            return AntiResult::NotFound(Date::now() / 1000. - start_time, None);
         }
-        let cur = row[n..]
+        let runtime = Date::now() / 1000. - start_time;
+        let (x, y) = match anti_anagram_hash(Parameters {
+            length: length - 2 * pad,
+            alphabet: Alphabet::new("()"),
+            timeout: timeout - runtime,
+            ..parameters.clone()
+        }) {
+            AntiResult::Ok(_, x, y) => (x, y),
+            result => return result,
+        };
+        // Only the per-position difference matters for the collision, so
+        // the shared positions are refilled to keep both sequences balanced.
+        let d = std::iter::repeat_n(0, pad)
+            .chain(
+                x.chars()
+                    .zip(y.chars())
+                    .map(|(p, q)| (p == ')') as i64 - (q == ')') as i64),
+            )
+            .chain(std::iter::repeat_n(0, pad))
+            .collect::<Vec<_>>();
+        let free = d.iter().filter(|&&val| val == 0).count();
+        let mut seen = 0;
+        let (a, b): (String, String) = d
             .iter()
-            .map(|val| {
-                if val >= &BigInt::ZERO {
-                    val.clone()
-                } else {
-                    -val
+            .map(|&val| match val {
+                1 => (')', '('),
+                -1 => ('(', ')'),
+                _ => {
+                    seen += 1;
+                    let c = if seen <= free / 2 { '(' } else { ')' };
+                    (c, c)
                 }
             })
-            .max()
-            .unwrap();
-        if cur < BigInt::from_usize(sigma).unwrap() {
-            let mut a = String::new();
-            let mut b = String::new();
-            for i in 0..length {
-                let diff: i32 = row[n + i].clone().try_into().unwrap();
-                if diff >= 0 {
-                    a.push('a');
-                    b.push(('a' as u8 + diff as u8) as char);
-                } else {
-                    a.push(('a' as u8 + (-diff) as u8) as char);
-                    b.push('a');
-                }
+            .unzip();
+        let (_, min_a) = balance(&a);
+        let (_, min_b) = balance(&b);
+        if min_a.min(min_b) == 0 {
+            break (a, b);
+        }
+        pad += -min_a.min(min_b) as usize;
+    };
+    if balance(&a) != (0, 0) || balance(&b) != (0, 0) || !check(&a, &b, &modulo, &base) {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Ok(Date::now() / 1000. - start_time, a, b)
+}
+
+pub fn anti_permutation_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        length,
+        modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        ..
+    } = parameters;
+    if length < 2 {
+        return AntiResult::NotFound(0., None);
+    }
+    let n = modulo.len();
+    let coefficients = (0..n)
+        .map(|i| {
+            let pow = powers(&base[i], &modulo[i], length);
+            (0..length / 2)
+                .map(|j| &pow[2 * j] - &pow[2 * j + 1])
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    let b = lattice(&coefficients, &modulo, &lambda);
+    let mut l2 = L2::new(b, delta, eta, precision, BigInt::from(2), timeout, n);
+    l2.reduce();
+    let row = match l2.solution() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let mut a = (1..=length)
+        .map(|val| BigInt::from_usize(val).unwrap())
+        .collect::<Vec<_>>();
+    let mut b = a.clone();
+    for (j, d) in row.iter().enumerate() {
+        if d > &BigInt::ZERO {
+            b.swap(2 * j, 2 * j + 1);
+        } else if d < &BigInt::ZERO {
+            a.swap(2 * j, 2 * j + 1);
+        }
+    }
+    if (0..n)
+        .any(|i| hash_values(&a, &modulo[i], &base[i]) != hash_values(&b, &modulo[i], &base[i]))
+    {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Array(l2.runtime(), a, b)
+}
+
+pub fn anti_sparse_hash(parameters: Parameters) -> AntiResult {
+    let start_time = Date::now() / 1000.;
+    let Parameters {
+        length,
+        modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        alphabet,
+        sparsity,
+        ..
+    } = parameters;
+    let n = modulo.len();
+    let limit = sparsity.unwrap().min(length);
+    let bits = modulo.iter().map(|val| val.bits()).sum::<u64>() as f64;
+    let mut k =
+        ((bits / ((2 * alphabet.span() - 1) as f64).log2()).ceil() as usize).clamp(1, limit);
+    let row = loop {
+        let coefficients = (0..n)
+            .map(|i| powers(&base[i], &modulo[i], k))
+            .collect::<Vec<_>>();
+        let b = lattice(&coefficients, &modulo, &lambda);
+        let runtime = Date::now() / 1000. - start_time;
+        let mut l2 = L2::new(
+            b,
+            delta.clone(),
+            eta.clone(),
+            precision,
+            alphabet.sigma(),
+            timeout - runtime,
+            n,
+        );
+        l2.differences = Some(alphabet.differences());
+        l2.reduce();
+        let weight = |row: &[BigInt]| row.iter().filter(|val| !val.is_zero()).count();
+        match l2.solution() {
+            Ok(row) => {
+                break l2
+                    .b
+                    .iter()
+                    .filter(|other| {
+                        other[..n].iter().all(|val| val.is_zero())
+                            && other[n..].iter().all(|val| alphabet.pair(val).is_some())
+                    })
+                    .map(|other| other[n..].to_vec())
+                    .min_by_key(|other| weight(other))
+                    .unwrap_or(row);
             }
-            if !check(&a, &b, modulo, base) {
-                return AntiResult::Unknown;
+            Err(AntiResult::NotFound(_, best)) if k >= limit => {
+                return AntiResult::NotFound(Date::now() / 1000. - start_time, best)
             }
-            return AntiResult::Ok(l2.runtime(), a, b);
-        }
-        if best.is_none() || best.clone().unwrap() > cur {
-            best = Some(cur);
-            best_vec = Some(row[n..].iter().map(|val| val.clone()).collect());
+            Err(AntiResult::NotFound(..)) => k += 1,
+            Err(result) => return result,
         }
+    };
+    let (mut a, mut b) = decode(&row, &alphabet);
+    for _ in k..length {
+        a.push(alphabet.first());
+        b.push(alphabet.first());
+    }
+    if !check(&a, &b, &modulo, &base) {
+        return AntiResult::Unknown;
     }
+    AntiResult::Ok(Date::now() / 1000. - start_time, a, b)
+}
+
+pub fn anti_report_hash(parameters: Parameters) -> AntiResult {
+    let Parameters {
+        length,
+        modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        ..
+    } = parameters;
+    let n = modulo.len();
+    let coefficients = (0..n)
+        .map(|i| powers(&base[i], &modulo[i], length))
+        .collect::<Vec<_>>();
+    let b = lattice(&coefficients, &modulo, &lambda);
+    let mut l2 = L2::new(b, delta, eta, precision, BigInt::one(), timeout, n);
+    l2.reduce();
     if l2.check_time_out() {
-        return AntiResult::TimeOut(best_vec);
-    } else {
-        AntiResult::NotFound(l2.runtime(), best_vec)
+        return AntiResult::TimeOut(None);
     }
+    AntiResult::Report(l2.runtime(), l2.row_maxes())
+}
+
+pub fn anti_multi_hash(parameters: Parameters) -> AntiResult {
+    let count = parameters.count;
+    let modulo = parameters.modulo.clone();
+    let base = parameters.base.clone();
+    let (time, a, b) = match anti_hash(Parameters {
+        count: 2,
+        ..parameters
+    }) {
+        AntiResult::Ok(time, a, b) => (time, a, b),
+        result => return result,
+    };
+    let mut m = 0;
+    while 1 << m < count {
+        m += 1;
+    }
+    let strings = (0..count)
+        .map(|k| {
+            (0..m)
+                .map(|j| {
+                    if k >> j & 1 == 1 {
+                        b.as_str()
+                    } else {
+                        a.as_str()
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    if strings
+        .iter()
+        .any(|s| !check(&strings[0], s, &modulo, &base))
+    {
+        return AntiResult::Unknown;
+    }
+    let hashes = modulo
+        .iter()
+        .zip(base.iter())
+        .map(|(modulo, base)| hash(&strings[0], modulo, base))
+        .collect();
+    AntiResult::Multi(time, strings, hashes)
+}
+
+pub fn anti_hash(parameters: Parameters) -> AntiResult {
+    if parameters.crc.is_some() {
+        return anti_crc_hash(parameters);
+    }
+    if parameters.checksum.is_some() {
+        return anti_checksum_hash(parameters);
+    }
+    if parameters.fnv.is_some() {
+        return anti_birthday_hash(parameters);
+    }
+    if parameters.zobrist.is_some() {
+        return anti_zobrist_hash(parameters);
+    }
+    if parameters.multiset.is_some() {
+        return anti_multiset_hash(parameters);
+    }
+    if parameters.count > 2 {
+        return anti_multi_hash(parameters);
+    }
+    if parameters.target.is_some() {
+        return anti_second_preimage_hash(parameters);
+    }
+    if parameters.hashes.is_some() {
+        return anti_preimage_hash(parameters);
+    }
+    if parameters.substring {
+        return anti_substring_hash(parameters);
+    }
+    if parameters.range.is_some() {
+        return anti_array_hash(parameters);
+    }
+    if parameters.template.is_some() {
+        return anti_template_hash(parameters);
+    }
+    if parameters.grid.is_some() {
+        return anti_grid_hash(parameters);
+    }
+    if parameters.length_b.is_some() {
+        return anti_unequal_hash(parameters);
+    }
+    if parameters.report {
+        return anti_report_hash(parameters);
+    }
+    if parameters.sparsity.is_some() {
+        return anti_sparse_hash(parameters);
+    }
+    if parameters.permutation {
+        return anti_permutation_hash(parameters);
+    }
+    if parameters.brackets {
+        return anti_bracket_hash(parameters);
+    }
+    if parameters.anagram {
+        return anti_anagram_hash(parameters);
+    }
+    if parameters.nested {
+        return anti_nested_hash(parameters);
+    }
+    if parameters.palindrome {
+        return anti_palindrome_hash(parameters);
+    }
+    if parameters.palindromic {
+        return anti_palindromic_hash(parameters);
+    }
+    let Parameters {
+        length,
+        modulo,
+        base,
+        lambda,
+        delta,
+        eta,
+        precision,
+        timeout,
+        alphabet,
+        weights,
+        ..
+    } = parameters;
+    let n = modulo.len();
+    let coefficients = weights.unwrap_or_else(|| {
+        (0..n)
+            .map(|i| powers(&base[i], &modulo[i], length))
+            .collect()
+    });
+    let b = lattice(&coefficients, &modulo, &lambda);
+    let mut l2 = L2::new(b, delta, eta, precision, alphabet.sigma(), timeout, n);
+    l2.differences = Some(alphabet.differences());
+    l2.reduce();
+    let row = match l2.solution() {
+        Ok(row) => row,
+        Err(result) => return result,
+    };
+    let (a, b) = decode(&row, &alphabet);
+    if a == b
+        || (0..n).any(|i| {
+            weighted_hash(&a, &modulo[i], &coefficients[i])
+                != weighted_hash(&b, &modulo[i], &coefficients[i])
+        })
+    {
+        return AntiResult::Unknown;
+    }
+    AntiResult::Ok(l2.runtime(), a, b)
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+
+    fn pair(alphabet: &Alphabet, k: i64) -> Option<(char, char)> {
+        alphabet.pair(&BigInt::from(k))
+    }
+
+    #[test]
+    fn alphabet_acgt() {
+        let alphabet = Alphabet::new("TGCAA");
+        assert_eq!(alphabet.chars, ['A', 'C', 'G', 'T']);
+        assert_eq!(alphabet.gap, 2);
+        assert_eq!(alphabet.progression, ['A', 'C']);
+        assert_eq!(alphabet.span(), 4);
+        assert_eq!(alphabet.differences(), [true; 4]);
+        assert_eq!(pair(&alphabet, 1), Some(('A', 'C')));
+        assert_eq!(pair(&alphabet, 2), Some(('C', 'G')));
+        assert_eq!(pair(&alphabet, 3), Some(('A', 'G')));
+        assert_eq!(pair(&alphabet, -1), Some(('C', 'A')));
+        assert_eq!(pair(&alphabet, 4), None);
+    }
+
+    #[test]
+    fn alphabet_binary() {
+        let alphabet = Alphabet::new("01");
+        assert_eq!(alphabet.gap, 1);
+        assert_eq!(alphabet.progression, ['0', '1']);
+        assert_eq!(alphabet.span(), 2);
+        assert_eq!(pair(&alphabet, 1), Some(('0', '1')));
+        assert_eq!(pair(&alphabet, 2), None);
+    }
+
+    #[test]
+    fn alphabet_with_different_step() {
+        let alphabet = Alphabet::new("adgjb");
+        assert_eq!(alphabet.gap, 1);
+        assert_eq!(alphabet.progression, ['a', 'd', 'g', 'j']);
+        assert_eq!(alphabet.step(), BigInt::from(3));
+        assert_eq!(alphabet.span(), 10);
+        assert_eq!(
+            alphabet.differences(),
+            [true, true, true, true, false, true, true, false, true, true]
+        );
+        assert_eq!(pair(&alphabet, 2), Some(('b', 'd')));
+        assert_eq!(pair(&alphabet, 4), None);
+        assert_eq!(alphabet.value(&BigInt::from(2)), 'g');
+    }
+
+    #[test]
+    fn embedding_decode() {
+        let mut embedding = Embedding::new(
+            vec![BigInt::ZERO, BigInt::ZERO],
+            vec![BigInt::from(3), BigInt::one()],
+        );
+        let row = |values: [i32; 3]| values.map(BigInt::from);
+        let x = Some(vec![BigInt::from(2), BigInt::one()]);
+        assert_eq!(embedding.decode(&row([1, 3, 3])), x);
+        assert_eq!(embedding.decode(&row([-1, -3, -3])), x);
+        assert_eq!(embedding.decode(&row([2, 3, 3])), None);
+        assert_eq!(embedding.decode(&row([1, 3, 1])), None);
+        assert_eq!(embedding.decode(&row([9, 3, 3])), None);
+        embedding.exclude = x;
+        assert_eq!(embedding.decode(&row([1, 3, 3])), None);
+    }
+
+    #[test]
+    fn bracket_balance() {
+        assert_eq!(balance("(())"), (0, 0));
+        assert_eq!(balance("(()"), (1, 0));
+        assert_eq!(balance("())("), (0, -1));
+    }
+}
@@ -0,0 +1,120 @@
+use std::{collections::HashMap, str::FromStr};
+
+use web_sys::js_sys::Date;
+
+use crate::{
+    anti_hash::{Alphabet, AntiResult},
+    prng::{Engine, SplitMix64},
+};
+
+const TABLE: usize = 1 << 22;
+
+pub trait Round {
+    fn bits(&self) -> u32;
+    fn init(&self) -> u64;
+    fn round(&self, state: u64, byte: u8) -> u64;
+    fn hash(&self, data: &[u8]) -> u64 {
+        data.iter()
+            .fold(self.init(), |state, &byte| self.round(state, byte))
+    }
+}
+
+#[derive(Clone)]
+pub struct Fnv {
+    xor_first: bool,
+}
+
+impl FromStr for Fnv {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let xor_first = match s {
+            "fnv1_32" => false,
+            "fnv1a_32" => true,
+            _ => return Err(()),
+        };
+        Ok(Self { xor_first })
+    }
+}
+
+impl Round for Fnv {
+    fn bits(&self) -> u32 {
+        32
+    }
+    fn init(&self) -> u64 {
+        0x811c9dc5
+    }
+    fn round(&self, state: u64, byte: u8) -> u64 {
+        if self.xor_first {
+            (state ^ byte as u64).wrapping_mul(0x01000193) & u32::MAX as u64
+        } else {
+            state.wrapping_mul(0x01000193) & u32::MAX as u64 ^ byte as u64
+        }
+    }
+}
+
+pub fn birthday<R: Round>(
+    round: &R,
+    alphabet: &Alphabet,
+    length: usize,
+    timeout: f64,
+) -> AntiResult {
+    let start_time = Date::now() / 1000.;
+    let chars = alphabet.chars();
+    let sigma = chars.len() as u64;
+    let mut prefix = 1;
+    let mut total = sigma;
+    while prefix < length && total >> (round.bits() + 8).min(63) == 0 {
+        prefix += 1;
+        total = total.saturating_mul(sigma);
+    }
+    let random = total >> (round.bits() / 2 + 4).min(63) != 0;
+    let mut engine = SplitMix64::new(0);
+    let build = |mut index: u64, length: usize| {
+        (0..length)
+            .map(|_| {
+                let c = chars[(index % sigma) as usize];
+                index /= sigma;
+                c
+            })
+            .collect::<String>()
+    };
+    let mut seen = HashMap::new();
+    for step in 0..total {
+        if step % (1 << 12) == 0 && Date::now() / 1000. - start_time > timeout {
+            return AntiResult::TimeOut(None);
+        }
+        // Bound the table so wide hashes give up instead of exhausting memory.
+        if seen.len() >= TABLE {
+            break;
+        }
+        let index = if random { engine.next() % total } else { step };
+        let state = build(index, prefix)
+            .bytes()
+            .fold(round.init(), |state, byte| round.round(state, byte));
+        if let Some(&other) = seen.get(&state).filter(|&&other| other != index) {
+            let (a, b) = (build(other, length), build(index, length));
+            if round.hash(a.as_bytes()) != round.hash(b.as_bytes()) {
+                return AntiResult::Unknown;
+            }
+            return AntiResult::Ok(Date::now() / 1000. - start_time, a, b);
+        }
+        seen.insert(state, index);
+    }
+    AntiResult::NotFound(Date::now() / 1000. - start_time, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv_reference() {
+        let fnv1 = "fnv1_32".parse::<Fnv>().unwrap();
+        let fnv1a = "fnv1a_32".parse::<Fnv>().unwrap();
+        assert_eq!(fnv1.hash(b""), 0x811c9dc5);
+        assert_eq!(fnv1.hash(b"a"), 0x050c5d7e);
+        assert_eq!(fnv1.hash(b"foobar"), 0x31f0b262);
+        assert_eq!(fnv1a.hash(b"a"), 0xe40c292c);
+        assert_eq!(fnv1a.hash(b"foobar"), 0xbf9cf968);
+    }
+}
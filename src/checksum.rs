@@ -0,0 +1,127 @@
+use std::str::FromStr;
+
+use bigdecimal::{num_bigint::BigInt, FromPrimitive};
+
+#[derive(Clone, Copy)]
+pub enum Checksum {
+    Adler32,
+    Fletcher16,
+    Fletcher32,
+}
+
+impl FromStr for Checksum {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "adler32" => Ok(Self::Adler32),
+            "fletcher16" => Ok(Self::Fletcher16),
+            "fletcher32" => Ok(Self::Fletcher32),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Checksum {
+    fn modulo(&self) -> u64 {
+        match self {
+            Self::Adler32 => 65521,
+            Self::Fletcher16 => 255,
+            Self::Fletcher32 => 65535,
+        }
+    }
+    fn words(&self, data: &[u8]) -> Vec<u64> {
+        match self {
+            Self::Fletcher32 => data
+                .chunks(2)
+                .map(|pair| pair[0] as u64 | (*pair.get(1).unwrap_or(&0) as u64) << 8)
+                .collect(),
+            _ => data.iter().map(|&byte| byte as u64).collect(),
+        }
+    }
+    pub fn moduli(&self) -> Vec<BigInt> {
+        vec![BigInt::from_u64(self.modulo()).unwrap(); 2]
+    }
+    pub fn weights(&self, length: usize) -> Vec<Vec<BigInt>> {
+        let (width, scale) = match self {
+            Self::Fletcher32 => (2, 256),
+            _ => (1, 1),
+        };
+        let words = length.div_ceil(width);
+        let (sum, weighted) = (0..length)
+            .map(|i| {
+                let factor = u64::pow(scale, (i % width) as u32);
+                (
+                    BigInt::from_u64(factor).unwrap(),
+                    BigInt::from_u64(factor * (words - i / width) as u64).unwrap(),
+                )
+            })
+            .unzip();
+        vec![sum, weighted]
+    }
+    pub fn checksum(&self, data: &[u8]) -> u64 {
+        let modulo = self.modulo();
+        let (mut sum, mut weighted) = match self {
+            Self::Adler32 => (1, 0),
+            _ => (0, 0),
+        };
+        for word in self.words(data) {
+            sum = (sum + word) % modulo;
+            weighted = (weighted + sum) % modulo;
+        }
+        let shift = match self {
+            Self::Fletcher16 => 8,
+            _ => 16,
+        };
+        weighted << shift | sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adler32_reference() {
+        assert_eq!(Checksum::Adler32.checksum(b"Wikipedia"), 0x11E60398);
+    }
+
+    #[test]
+    fn fletcher_reference() {
+        assert_eq!(Checksum::Fletcher16.checksum(b"abcde"), 0xC8F0);
+        assert_eq!(Checksum::Fletcher32.checksum(b"abcde"), 0xF04FC729);
+        assert_eq!(Checksum::Fletcher32.checksum(b"abcdef"), 0x56502D2A);
+    }
+
+    #[test]
+    fn weights_match_checksum() {
+        for checksum in [
+            Checksum::Adler32,
+            Checksum::Fletcher16,
+            Checksum::Fletcher32,
+        ] {
+            for data in [&b"Wikipedia"[..], b"abcdef", b"\xff\xfe\x01"] {
+                let weights = checksum.weights(data.len());
+                let modulo = BigInt::from_u64(checksum.modulo()).unwrap();
+                let (sum, weighted) = match checksum {
+                    Checksum::Adler32 => (BigInt::from(1), BigInt::from(data.len())),
+                    _ => (BigInt::ZERO, BigInt::ZERO),
+                };
+                let dot = |weights: &[BigInt], init: BigInt| {
+                    let total = weights
+                        .iter()
+                        .zip(data)
+                        .map(|(w, &byte)| w * byte)
+                        .sum::<BigInt>();
+                    u64::try_from((init + total) % &modulo).unwrap()
+                };
+                let shift = match checksum {
+                    Checksum::Fletcher16 => 8,
+                    _ => 16,
+                };
+                let value = checksum.checksum(data);
+                assert_eq!(dot(&weights[0], sum), value & ((1 << shift) - 1));
+                assert_eq!(dot(&weights[1], weighted), value >> shift);
+            }
+        }
+    }
+}
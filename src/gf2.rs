@@ -0,0 +1,106 @@
+#[derive(Clone)]
+pub struct Crc {
+    pub width: u32,
+    pub poly: u64,
+    pub init: u64,
+    pub xorout: u64,
+    pub reflect: bool,
+}
+
+impl Crc {
+    pub fn crc32() -> Self {
+        Self {
+            width: 32,
+            poly: 0x04C11DB7,
+            init: 0xFFFFFFFF,
+            xorout: 0xFFFFFFFF,
+            reflect: true,
+        }
+    }
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.width)
+    }
+    pub fn checksum(&self, data: &[u8]) -> u64 {
+        let mask = self.mask();
+        let mut crc = self.init & mask;
+        if self.reflect {
+            let poly = (self.poly & mask).reverse_bits() >> (64 - self.width);
+            for &byte in data {
+                crc ^= byte as u64;
+                for _ in 0..8 {
+                    crc = if crc & 1 == 1 {
+                        (crc >> 1) ^ poly
+                    } else {
+                        crc >> 1
+                    };
+                }
+            }
+        } else {
+            let top = 1 << (self.width - 1);
+            for &byte in data {
+                crc ^= (byte as u64) << (self.width - 8);
+                for _ in 0..8 {
+                    crc = if crc & top != 0 {
+                        (crc << 1) ^ (self.poly & mask)
+                    } else {
+                        crc << 1
+                    } & mask;
+                }
+            }
+        }
+        (crc ^ self.xorout) & mask
+    }
+}
+
+pub fn kernel(vectors: &[u64]) -> Option<Vec<usize>> {
+    let n = vectors.len();
+    let mut basis: Vec<Option<(u64, Vec<bool>)>> = vec![None; 64];
+    for (i, &v) in vectors.iter().enumerate() {
+        let mut v = v;
+        let mut combination = vec![false; n];
+        combination[i] = true;
+        for bit in (0..64).rev() {
+            if v >> bit & 1 == 0 {
+                continue;
+            }
+            match &basis[bit] {
+                Some((w, other)) => {
+                    v ^= w;
+                    for (x, y) in combination.iter_mut().zip(other.iter()) {
+                        *x ^= y;
+                    }
+                }
+                None => {
+                    basis[bit] = Some((v, combination.clone()));
+                    break;
+                }
+            }
+        }
+        if v == 0 {
+            return Some((0..n).filter(|&j| combination[j]).collect());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_check() {
+        assert_eq!(Crc::crc32().checksum(b"123456789"), 0xCBF43926);
+    }
+
+    #[test]
+    fn crc_masks_wide_poly() {
+        let crc = Crc {
+            width: 16,
+            poly: 0x11021,
+            init: 0xFFFF,
+            xorout: 0,
+            reflect: false,
+        };
+        assert_eq!(crc.checksum(b"123456789"), 0x29B1);
+    }
+}
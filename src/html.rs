@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{collections::BTreeMap, str::FromStr};
 
 use bigdecimal::{num_bigint::BigInt, BigDecimal, FromPrimitive, One};
 use wasm_bindgen::{closure::Closure, prelude::wasm_bindgen, JsCast, JsValue};
@@ -6,7 +6,14 @@ use web_sys::{
     Document, Element, HtmlButtonElement, HtmlDivElement, HtmlElement, HtmlInputElement, Node,
 };
 
-use crate::anti_hash::{anti_hash, Parameters};
+use crate::{
+    anti_hash::{anti_hash, Alphabet, Parameters},
+    birthday::Fnv,
+    checksum::Checksum,
+    gf2::Crc,
+    prng::{bases, candidates, Generator},
+    recover::{recover_bases, LIMIT},
+};
 const N: usize = 100;
 const DEFUALT_N: usize = 4;
 const DEFAULT_MODULO: [&str; DEFUALT_N] = [
@@ -160,6 +167,20 @@ fn get_value_parsed<T: FromStr>(id: &str, td: &str) -> Result<T, String> {
     }
 }
 
+fn per_pair_input() -> Option<&'static str> {
+    [
+        (
+            "rows",
+            !get_value("rows").is_empty() || !get_value("columns").is_empty(),
+        ),
+        ("weights", !get_value("weights").trim().is_empty()),
+        ("multiset", !get_value("multiset").trim().is_empty()),
+        ("target hashes", !get_value("hashes").trim().is_empty()),
+    ]
+    .into_iter()
+    .find_map(|(name, active)| active.then_some(name))
+}
+
 fn get_inputs() -> Result<Parameters, String> {
     let number: usize = get_value_parsed("number", "an unsigned 64-bit integer")?;
     if number == 0 || number > N {
@@ -169,6 +190,15 @@ fn get_inputs() -> Result<Parameters, String> {
     if length == 0 {
         return Err("[length] should be postive.".to_string());
     }
+    let length_b = if get_value("length_b").is_empty() {
+        None
+    } else {
+        let length_b = get_value_parsed("length_b", "an unsigned 64-bit integer")?;
+        if length_b == 0 {
+            return Err("[length of the second string] should be postive.".to_string());
+        }
+        Some(length_b).filter(|&length_b| length_b != length)
+    };
     let mut modulo = Vec::new();
     for i in 0..number {
         modulo.push(get_value_parsed(&format!("modulo_{}", i), "an integer")?);
@@ -177,7 +207,76 @@ fn get_inputs() -> Result<Parameters, String> {
     for i in 0..number {
         base.push(get_value_parsed(&format!("base_{}", i), "an integer")?);
     }
-    for i in 0..number {
+    if !get_value("generator").is_empty() {
+        let generator: Generator = get_value("generator").parse().map_err(|_| {
+            "[generator] should be one of mt19937, mt19937_64, rand, java and splitmix64."
+                .to_string()
+        })?;
+        let seed = get_value_parsed("seed", "an unsigned 64-bit integer")?;
+        let lo: u64 = get_value_parsed("base_lo", "an unsigned 64-bit integer")?;
+        let hi: u64 = get_value_parsed("base_hi", "an unsigned 64-bit integer")?;
+        if lo > hi {
+            return Err("[base_lo] should not be greater than [base_hi].".to_string());
+        }
+        if get_value("seed_hi").is_empty() {
+            base = bases(generator, seed, lo, hi, number);
+        } else {
+            let seed_hi = get_value_parsed("seed_hi", "an unsigned 64-bit integer")?;
+            if seed > seed_hi {
+                return Err("[seed] should not be greater than [seed_hi].".to_string());
+            }
+            if (seed_hi - seed)
+                .checked_add(1)
+                .and_then(|seeds| seeds.checked_mul(number as u64))
+                .is_none_or(|pairs| pairs > N as u64)
+            {
+                return Err(format!(
+                    "[seed] to [seed_hi] should give at most {} modulo-base pairs in total.",
+                    N
+                ));
+            }
+            if let Some(name) = per_pair_input() {
+                return Err(format!(
+                    "[seed_hi] and [{}] cannot be combined, as the latter is given per modulo-base pair.",
+                    name
+                ));
+            }
+            (modulo, base) = candidates(generator, seed..=seed_hi, lo, hi, &modulo);
+        }
+    }
+    let samples = get_value("samples");
+    if !samples.trim().is_empty() {
+        let tokens = samples.split_whitespace().collect::<Vec<_>>();
+        if tokens.len() % 2 != 0 {
+            return Err("[samples] should alternate between strings and hashes.".to_string());
+        }
+        if let Some(name) = per_pair_input() {
+            return Err(format!(
+                "[samples] and [{}] cannot be combined, as the latter is given per modulo-base pair.",
+                name
+            ));
+        }
+        let samples = tokens
+            .chunks(2)
+            .map(|pair| Ok((pair[0].to_string(), pair[1].parse::<BigInt>()?)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_: bigdecimal::num_bigint::ParseBigIntError| {
+                "[samples] hashes should be integers.".to_string()
+            })?;
+        let mut pairs = Vec::new();
+        for (i, modulo) in modulo.iter().enumerate() {
+            let recovered = recover_bases(modulo, &samples).ok_or(format!(
+                "[samples] admit more than {} bases modulo [modulo_{}], add more samples.",
+                LIMIT, i
+            ))?;
+            if recovered.is_empty() {
+                return Err(format!("[samples] admit no base modulo [modulo_{}].", i));
+            }
+            pairs.extend(recovered.into_iter().map(|base| (modulo.clone(), base)));
+        }
+        (modulo, base) = pairs.into_iter().unzip();
+    }
+    for i in 0..modulo.len() {
         if base[i] < BigInt::ZERO {
             return Err(format!("[base_{}] should be non-negative.", i));
         }
@@ -188,6 +287,135 @@ fn get_inputs() -> Result<Parameters, String> {
             ));
         }
     }
+    let grid = if get_value("rows").is_empty() && get_value("columns").is_empty() {
+        None
+    } else {
+        let rows = get_value_parsed("rows", "an unsigned 64-bit integer")?;
+        let columns = get_value_parsed("columns", "an unsigned 64-bit integer")?;
+        if rows == 0 || columns == 0 {
+            return Err("[rows] and [columns] should be positive.".to_string());
+        }
+        Some((rows, columns))
+    };
+    let mut column_base = Vec::new();
+    if grid.is_some() {
+        for i in 0..number {
+            column_base.push(get_value_parsed(
+                &format!("column_base_{}", i),
+                "an integer",
+            )?);
+        }
+        for i in 0..number {
+            if column_base[i] < BigInt::ZERO || column_base[i] >= modulo[i] {
+                return Err(format!(
+                    "[column_base_{}] should be between 0 and [modulo_{}] - 1.",
+                    i, i
+                ));
+            }
+        }
+    }
+    let weights = get_value("weights");
+    let weights = if weights.trim().is_empty() {
+        None
+    } else {
+        let weights = weights
+            .split(';')
+            .map(|list| {
+                list.split_whitespace()
+                    .map(|val| val.parse::<BigInt>())
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| "[weights] should be integers.".to_string())?;
+        if weights.len() != number {
+            return Err(format!(
+                "[weights] should contain exactly {} lists separated by ';'.",
+                number
+            ));
+        }
+        if weights[0].is_empty() || weights.iter().any(|list| list.len() != weights[0].len()) {
+            return Err("[weights] lists should be non-empty and of equal length.".to_string());
+        }
+        Some(weights)
+    };
+    let crc = if get_value("crc_width").is_empty() {
+        None
+    } else {
+        let width = get_value_parsed("crc_width", "an unsigned 32-bit integer")?;
+        if !(8..=64).contains(&width) {
+            return Err("[crc_width] should be between 8 and 64.".to_string());
+        }
+        let get_hex = |id: &str, default: u64| {
+            let value = get_value(id);
+            if value.is_empty() {
+                return Ok(default);
+            }
+            let value = u64::from_str_radix(value.trim_start_matches("0x"), 16)
+                .map_err(|_| format!("[{}] should be a hexadecimal 64-bit integer.", id))?;
+            if width < 64 && value >> width != 0 {
+                return Err(format!("[{}] should fit in [crc_width] bits.", id));
+            }
+            Ok(value)
+        };
+        let crc32 = Crc::crc32();
+        Some(Crc {
+            width,
+            poly: get_hex("crc_poly", crc32.poly)?,
+            init: get_hex("crc_init", crc32.init)?,
+            xorout: get_hex("crc_xorout", crc32.xorout)?,
+            reflect: document()
+                .get_element_by_id("crc_reflect")
+                .unwrap()
+                .dyn_into::<HtmlInputElement>()
+                .unwrap()
+                .checked(),
+        })
+    };
+    let zobrist = get_value("zobrist");
+    let zobrist = if zobrist.trim().is_empty() {
+        None
+    } else {
+        Some(
+            zobrist
+                .split_whitespace()
+                .map(|val| match val.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16),
+                    None => val.parse(),
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| "[zobrist table] should be unsigned 64-bit integers.".to_string())?,
+        )
+    };
+    let multiset = get_value("multiset");
+    let multiset = if multiset.trim().is_empty() {
+        None
+    } else {
+        let multiset = multiset
+            .split(';')
+            .map(|list| {
+                list.split_whitespace()
+                    .map(|val| val.parse::<BigInt>())
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| "[multiset table] should be integers.".to_string())?;
+        if multiset.len() != number {
+            return Err(format!(
+                "[multiset table] should contain exactly {} lists separated by ';'.",
+                number
+            ));
+        }
+        if multiset[0].is_empty() || multiset.iter().any(|list| list.len() != multiset[0].len()) {
+            return Err(
+                "[multiset table] lists should be non-empty and of equal length.".to_string(),
+            );
+        }
+        Some(multiset)
+    };
+    let multiset_count = get_value_parsed("multiset_count", "an unsigned 64-bit integer")?;
+    if multiset_count < 2 {
+        return Err("[multiset count bound] should be at least 2.".to_string());
+    }
     let lambda = get_value_parsed("lambda", "an intger")?;
     if lambda <= BigInt::ZERO {
         return Err("[lambda] should be positive".to_string());
@@ -204,10 +432,108 @@ fn get_inputs() -> Result<Parameters, String> {
     if precision == 0 || precision > 100 {
         return Err("[precision] should be between 1 and 100".to_string());
     }
-    let sigma = get_value_parsed("sigma", "an unsigned 64-bit integer")?;
-    if sigma <= 1 || sigma > 26 {
-        return Err("[size of character set] should be between 2 and 26".to_string());
+    let alphabet = get_value("alphabet");
+    let alphabet = if alphabet.is_empty() {
+        let sigma = get_value_parsed("sigma", "an unsigned 64-bit integer")?;
+        if sigma <= 1 || sigma > 26 {
+            return Err("[size of character set] should be between 2 and 26".to_string());
+        }
+        ('a'..='z').take(sigma).collect::<String>()
+    } else {
+        alphabet
+    };
+    if alphabet.chars().all(|c| alphabet.starts_with(c)) {
+        return Err("[character set] should contain at least 2 distinct characters".to_string());
+    }
+    let checksum = get_value("checksum");
+    let checksum = if checksum.is_empty() {
+        None
+    } else {
+        if !alphabet.is_ascii() {
+            return Err("[character set] should be ASCII when attacking a checksum.".to_string());
+        }
+        Some(checksum.parse::<Checksum>().map_err(|_| {
+            "[checksum] should be one of adler32, fletcher16 and fletcher32.".to_string()
+        })?)
+    };
+    let fnv = get_value("fnv");
+    let fnv = if fnv.is_empty() {
+        None
+    } else {
+        Some(
+            fnv.parse::<Fnv>()
+                .map_err(|_| "[fnv] should be one of fnv1_32 and fnv1a_32.".to_string())?,
+        )
+    };
+    let alphabet = Alphabet::new(&alphabet);
+    let count = get_value_parsed("count", "an unsigned 64-bit integer")?;
+    if count < 2 {
+        return Err("[number of strings] should be at least 2".to_string());
     }
+    let target = get_value("target");
+    let target = if target.is_empty() {
+        None
+    } else {
+        if target.chars().any(|c| !alphabet.contains(c)) {
+            return Err(
+                "[target string] should only contain characters in the character set".to_string(),
+            );
+        }
+        Some(target)
+    };
+    let hashes = get_value("hashes");
+    let hashes = if hashes.trim().is_empty() {
+        None
+    } else {
+        let hashes = hashes
+            .split_whitespace()
+            .map(|val| val.parse::<BigInt>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| "[target hashes] should be integers.".to_string())?;
+        if hashes.len() != number {
+            return Err(format!(
+                "[target hashes] should contain exactly {} values.",
+                number
+            ));
+        }
+        for i in 0..number {
+            if hashes[i] < BigInt::ZERO || hashes[i] >= modulo[i] {
+                return Err(format!(
+                    "[target hashes] value {} should be between 0 and [modulo_{}] - 1.",
+                    i, i
+                ));
+            }
+        }
+        Some(hashes)
+    };
+    let range = if get_value("lo").is_empty() && get_value("hi").is_empty() {
+        None
+    } else {
+        let lo: BigInt = get_value_parsed("lo", "an integer")?;
+        let hi: BigInt = get_value_parsed("hi", "an integer")?;
+        if lo >= hi {
+            return Err("[lo] should be strictly less than [hi].".to_string());
+        }
+        Some((lo, hi))
+    };
+    let template = get_value("template");
+    let template = if template.is_empty() {
+        None
+    } else {
+        if !template.contains('?') {
+            return Err("[template] should contain at least one '?'".to_string());
+        }
+        Some(template)
+    };
+    let sparsity = if get_value("sparsity").is_empty() {
+        None
+    } else {
+        let sparsity = get_value_parsed("sparsity", "an unsigned 64-bit integer")?;
+        if sparsity == 0 {
+            return Err("[sparsity] should be positive.".to_string());
+        }
+        Some(sparsity)
+    };
     let timeout = get_value_parsed("timeout", "a number")?;
     if timeout <= 0. {
         return Err("[timeout] should be positive".to_string());
@@ -218,16 +544,115 @@ fn get_inputs() -> Result<Parameters, String> {
         .dyn_into::<HtmlInputElement>()
         .unwrap()
         .checked();
+    let palindromic = document()
+        .get_element_by_id("palindromic")
+        .unwrap()
+        .dyn_into::<HtmlInputElement>()
+        .unwrap()
+        .checked();
+    let nested = document()
+        .get_element_by_id("nested")
+        .unwrap()
+        .dyn_into::<HtmlInputElement>()
+        .unwrap()
+        .checked();
+    let report = document()
+        .get_element_by_id("report")
+        .unwrap()
+        .dyn_into::<HtmlInputElement>()
+        .unwrap()
+        .checked();
+    let permutation = document()
+        .get_element_by_id("permutation")
+        .unwrap()
+        .dyn_into::<HtmlInputElement>()
+        .unwrap()
+        .checked();
+    let brackets = document()
+        .get_element_by_id("brackets")
+        .unwrap()
+        .dyn_into::<HtmlInputElement>()
+        .unwrap()
+        .checked();
+    let anagram = document()
+        .get_element_by_id("anagram")
+        .unwrap()
+        .dyn_into::<HtmlInputElement>()
+        .unwrap()
+        .checked();
+    let substring = document()
+        .get_element_by_id("substring")
+        .unwrap()
+        .dyn_into::<HtmlInputElement>()
+        .unwrap()
+        .checked();
+    if brackets && length % 2 != 0 {
+        return Err("[length] should be even for balanced bracket sequences.".to_string());
+    }
+    let modes = [
+        ("number of strings", count > 2),
+        ("target string", target.is_some()),
+        ("target hashes", hashes.is_some()),
+        ("substring", substring),
+        ("lo", range.is_some()),
+        ("template", template.is_some()),
+        ("rows", grid.is_some()),
+        ("length of the second string", length_b.is_some()),
+        ("report", report),
+        ("sparsity", sparsity.is_some()),
+        ("permutation", permutation),
+        ("brackets", brackets),
+        ("anagram", anagram),
+        ("nested", nested),
+        ("palindrome", palindrome),
+        ("palindromic", palindromic),
+        ("crc_width", crc.is_some()),
+        ("checksum", checksum.is_some()),
+        ("fnv", fnv.is_some()),
+        ("zobrist table", zobrist.is_some()),
+        ("multiset table", multiset.is_some()),
+        ("weights", weights.is_some()),
+    ]
+    .into_iter()
+    .filter(|&(_, active)| active)
+    .map(|(name, _)| format!("[{}]", name))
+    .collect::<Vec<_>>();
+    if modes.len() > 1 {
+        return Err(format!("{} cannot be combined.", modes.join(" and ")));
+    }
     Ok(Parameters {
         length,
+        length_b,
         modulo,
         base,
         lambda,
         delta,
         eta,
         precision,
-        sigma,
+        alphabet,
         palindrome,
+        palindromic,
+        nested,
+        count,
+        target,
+        hashes,
+        substring,
+        range,
+        template,
+        anagram,
+        brackets,
+        permutation,
+        sparsity,
+        report,
+        grid,
+        column_base,
+        weights,
+        crc,
+        checksum,
+        fnv,
+        zobrist,
+        multiset,
+        multiset_count,
         timeout,
     })
 }
@@ -280,6 +705,11 @@ fn run_anti_hash() {
                 &element("div").with_text_content(&format!("time consumed: {}s", time)),
             )
             .unwrap();
+            log.append_child(&element("div").with_text_content(&format!(
+                "Hamming distance: {}",
+                a.chars().zip(b.chars()).filter(|(x, y)| x != y).count()
+            )))
+            .unwrap();
             let reverse_description = document().get_element_by_id("reverse_description").unwrap();
             reverse_description.set_text_content(Some(REVERSE_DESCRIPTION[0]));
             let string_a = document().get_element_by_id("string_a").unwrap();
@@ -288,6 +718,112 @@ fn run_anti_hash() {
             string_b.set_text_content(Some(&b));
             display("outputs", "block");
         }
+        crate::anti_hash::AntiResult::Multi(time, strings, hashes) => {
+            log.append_child(
+                &element("div").with_text_content(&format!("time consumed: {}s", time)),
+            )
+            .unwrap();
+            log.append_child(&element("div").with_text_content(&format!(
+                "The hashes of the following {} strings are {:?}",
+                strings.len(),
+                hashes
+            )))
+            .unwrap();
+            for s in strings {
+                log.append_child(&element("div").with_text_content(&s))
+                    .unwrap();
+            }
+        }
+        crate::anti_hash::AntiResult::Preimage(time, s, hashes) => {
+            log.append_child(
+                &element("div").with_text_content(&format!("time consumed: {}s", time)),
+            )
+            .unwrap();
+            log.append_child(
+                &element("div")
+                    .with_text_content(&format!("The following string has hashes {:?}", hashes)),
+            )
+            .unwrap();
+            log.append_child(&element("div").with_text_content(&s))
+                .unwrap();
+        }
+        crate::anti_hash::AntiResult::Substring(time, s, i, j, length) => {
+            log.append_child(
+                &element("div").with_text_content(&format!("time consumed: {}s", time)),
+            )
+            .unwrap();
+            log.append_child(&element("div").with_text_content(&format!(
+                "The substrings of length {} starting at {} and {} (0-indexed) have equal hashes",
+                length, i, j
+            )))
+            .unwrap();
+            log.append_child(&element("div").with_text_content(&s))
+                .unwrap();
+        }
+        crate::anti_hash::AntiResult::Array(time, a, b) => {
+            log.append_child(
+                &element("div").with_text_content(&format!("time consumed: {}s", time)),
+            )
+            .unwrap();
+            for array in [a, b] {
+                log.append_child(
+                    &element("div").with_text_content(
+                        &array
+                            .iter()
+                            .map(|val| val.to_string())
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                    ),
+                )
+                .unwrap();
+            }
+        }
+        crate::anti_hash::AntiResult::Grid(time, a, b) => {
+            log.append_child(
+                &element("div").with_text_content(&format!("time consumed: {}s", time)),
+            )
+            .unwrap();
+            for (name, grid) in [("A", a), ("B", b)] {
+                log.append_child(
+                    &element("div").with_text_content(&format!("The rows of grid {}:", name)),
+                )
+                .unwrap();
+                for row in grid {
+                    log.append_child(&element("div").with_text_content(&row))
+                        .unwrap();
+                }
+            }
+        }
+        crate::anti_hash::AntiResult::Report(time, row_maxes) => {
+            log.append_child(
+                &element("div").with_text_content(&format!("time consumed: {}s", time)),
+            )
+            .unwrap();
+            match row_maxes.first() {
+                Some(min) => {
+                    log.append_child(&element("div").with_text_content(&format!(
+                        "smallest size of character set: {}",
+                        min + 1
+                    )))
+                    .unwrap();
+                    let mut distribution = BTreeMap::new();
+                    for val in row_maxes {
+                        *distribution.entry(val).or_insert(0) += 1;
+                    }
+                    log.append_child(&element("div").with_text_content(&format!(
+                        "maximum absolute values of the reduced rows with zero hashes: {:?}",
+                        distribution
+                    )))
+                    .unwrap();
+                }
+                None => {
+                    log.append_child(
+                        &element("div").with_text_content("No reduced row has zero hashes."),
+                    )
+                    .unwrap();
+                }
+            }
+        }
         crate::anti_hash::AntiResult::Unknown => {
             log.set_text_content(Some("There exist unknown bugs."))
         }
@@ -332,6 +868,86 @@ fn main() {
             .into(),
     ]))
     .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content(
+            "generate grids hashed by row and column bases (overrides length): rows = ",
+        ),
+        input().with_type("number").with_id("rows").into(),
+        element("span").with_text_content(" columns = "),
+        input().with_type("number").with_id("columns").into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content(
+            "attack a CRC instead of the modulo-base pairs (blank fields default to CRC32): width = ",
+        ),
+        input().with_type("number").with_id("crc_width").into(),
+        element("span").with_text_content(" polynomial = 0x"),
+        input()
+            .with_id("crc_poly")
+            .with_atrribute("placeholder", "04C11DB7").into(),
+        element("span").with_text_content(" init = 0x"),
+        input()
+            .with_id("crc_init")
+            .with_atrribute("placeholder", "FFFFFFFF").into(),
+        element("span").with_text_content(" xorout = 0x"),
+        input()
+            .with_id("crc_xorout")
+            .with_atrribute("placeholder", "FFFFFFFF").into(),
+        element("span").with_text_content(" reflected:"),
+        input()
+            .with_type("checkbox")
+            .with_id("crc_reflect")
+            .with_atrribute("checked", "").into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content(
+            "attack a checksum instead of the modulo-base pairs (adler32, fletcher16 or fletcher32): ",
+        ),
+        input().with_id("checksum").into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content(
+            "attack FNV with a birthday search instead of the modulo-base pairs (fnv1_32 or fnv1a_32): ",
+        ),
+        input().with_id("fnv").into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content(
+            "zobrist table of 64-bit values, finds two sets of indices with equal XOR: ",
+        ),
+        input().with_id("zobrist").into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content(
+            "multiset hash table r, hashing to ∑ r[x], one list per pair separated by ';': ",
+        ),
+        input().with_id("multiset").into(),
+        element("span").with_text_content(" counts differ by less than: "),
+        input()
+            .with_type("number")
+            .with_default_value("26")
+            .with_atrribute("min", "2")
+            .with_id("multiset_count")
+            .into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content(
+            "per-position weights replacing the powers of base, one list per pair separated by ';' (overrides length): ",
+        ),
+        input().with_id("weights").into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content("length of the second string (defaults to length): "),
+        input().with_type("number").with_id("length_b").into(),
+    ]))
+    .unwrap();
     app.append_child(&div([
         element("span").with_text_content("number of modulo-base pairs: "),
         input()
@@ -362,12 +978,49 @@ fn main() {
                     .with_default_value(if i < DEFUALT_N { DEFAULT_BASE[i] } else { "0" })
                     .with_id(&format!("base_{}", i))
                     .into(),
+                element("span").with_text_content(&format!(" column_base_{}: ", i)),
+                input()
+                    .with_type("number")
+                    .with_default_value(if i < DEFUALT_N {
+                        DEFAULT_BASE[(i + 1) % DEFUALT_N]
+                    } else {
+                        "0"
+                    })
+                    .with_id(&format!("column_base_{}", i))
+                    .into(),
             ])
             .with_id(&format!("input_{}", i))
             .into(),
         )
         .unwrap();
     }
+    app.append_child(&div([
+        element("span").with_text_content(
+            "draw the bases from a seeded generator (mt19937, mt19937_64, rand, java or splitmix64) instead: generator = ",
+        ),
+        input().with_id("generator").into(),
+        element("span").with_text_content(" seed = "),
+        input().with_type("number").with_id("seed").into(),
+        element("span").with_text_content(" (optionally up to "),
+        input().with_type("number").with_id("seed_hi").into(),
+        element("span").with_text_content(&format!(
+            ", attacking every seed at once, at most {} pairs in total)",
+            N
+        )),
+        element("span").with_text_content(" range = ["),
+        input().with_type("number").with_id("base_lo").into(),
+        element("span").with_text_content(", "),
+        input().with_type("number").with_id("base_hi").into(),
+        element("span").with_text_content("]"),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content(
+            "recover the bases from observed samples, alternating strings and their hashes (every candidate base is attacked): ",
+        ),
+        input().with_id("samples").into(),
+    ]))
+    .unwrap();
     app.append_child(&element("br").into()).unwrap();
     app.append_child(&element("br").into()).unwrap();
     app.append_child(&div([
@@ -416,6 +1069,54 @@ fn main() {
             .into(),
     ]))
     .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content(
+            "character set (overrides size of character set; differences are taken as multiples of one step, so some characters may stay unused, and modes that pick characters rather than differences, such as target string, target hashes and different lengths, use its longest evenly spaced subset): ",
+        ),
+        input().with_id("alphabet").into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content("number of strings: "),
+        input()
+            .with_type("number")
+            .with_default_value("2")
+            .with_atrribute("min", "2")
+            .with_id("count")
+            .into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content("string to collide with (overrides length): "),
+        input().with_id("target").into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content("target hashes, one per pair (space-separated): "),
+        input().with_id("hashes").into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content(
+            "generate integer arrays with values in [lo, hi] instead of strings: lo = ",
+        ),
+        input().with_type("number").with_id("lo").into(),
+        element("span").with_text_content(" hi = "),
+        input().with_type("number").with_id("hi").into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span")
+            .with_text_content("template, where only '?' may differ (overrides length): "),
+        input().with_id("template").into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span")
+            .with_text_content("maximum number of differing positions (minimizes it when set): "),
+        input().with_type("number").with_id("sparsity").into(),
+    ]))
+    .unwrap();
     app.append_child(&div([
         element("span").with_text_content("timeout in seconds: "),
         input().with_default_value("60").with_id("timeout").into(),
@@ -427,6 +1128,54 @@ fn main() {
         input().with_type("checkbox").with_id("palindrome").into(),
     ]))
     .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content("ensure that both strings are palindromes:"),
+        input().with_type("checkbox").with_id("palindromic").into(),
+    ]))
+    .unwrap();
+    app.append_child(&div([
+        element("span").with_text_content(
+            "solve modulo-base pairs one by one, using the previous collision as the character set:",
+        ),
+        input().with_type("checkbox").with_id("nested").into(),
+    ]))
+    .unwrap();
+
+    app.append_child(&div([
+        element("span").with_text_content(
+            "generate one string with two colliding substrings of the given length (h[r] - h[l] q^(r-l)):",
+        ),
+        input().with_type("checkbox").with_id("substring").into(),
+    ]))
+    .unwrap();
+
+    app.append_child(&div([
+        element("span")
+            .with_text_content("ensure that two strings have the same character counts (only uses the longest evenly spaced run of the character set):"),
+        input().with_type("checkbox").with_id("anagram").into(),
+    ]))
+    .unwrap();
+
+    app.append_child(&div([
+        element("span")
+            .with_text_content("generate two balanced bracket sequences of the given even length (ignores character set):"),
+        input().with_type("checkbox").with_id("brackets").into(),
+    ]))
+    .unwrap();
+
+    app.append_child(&div([
+        element("span")
+            .with_text_content("generate two permutations of 1..length instead of strings:"),
+        input().with_type("checkbox").with_id("permutation").into(),
+    ]))
+    .unwrap();
+
+    app.append_child(&div([
+        element("span")
+            .with_text_content("only report the smallest size of character set for this length:"),
+        input().with_type("checkbox").with_id("report").into(),
+    ]))
+    .unwrap();
 
     app.append_child(&button().with_id("generate").with_text_content("generate"))
         .unwrap();
@@ -1,2 +1,7 @@
 mod anti_hash;
+mod birthday;
+mod checksum;
+mod gf2;
 mod html;
+mod prng;
+mod recover;
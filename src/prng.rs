@@ -0,0 +1,405 @@
+use std::{collections::HashSet, ops::RangeInclusive, str::FromStr};
+
+use bigdecimal::{num_bigint::BigInt, FromPrimitive};
+
+pub trait Engine {
+    const MAX: u64;
+    fn next(&mut self) -> u64;
+}
+
+pub struct Mt19937 {
+    state: [u32; 624],
+    index: usize,
+}
+
+impl Mt19937 {
+    pub fn new(seed: u32) -> Self {
+        let mut state = [0; 624];
+        state[0] = seed;
+        for i in 1..624 {
+            state[i] = 1812433253u32
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 30))
+                .wrapping_add(i as u32);
+        }
+        Self { state, index: 624 }
+    }
+    fn twist(&mut self) {
+        for i in 0..624 {
+            let y = (self.state[i] & 0x80000000) | (self.state[(i + 1) % 624] & 0x7fffffff);
+            let mut next = self.state[(i + 397) % 624] ^ (y >> 1);
+            if y & 1 == 1 {
+                next ^= 0x9908b0df;
+            }
+            self.state[i] = next;
+        }
+        self.index = 0;
+    }
+}
+
+impl Engine for Mt19937 {
+    const MAX: u64 = u32::MAX as u64;
+    fn next(&mut self) -> u64 {
+        if self.index >= 624 {
+            self.twist();
+        }
+        let mut y = self.state[self.index];
+        self.index += 1;
+        y ^= y >> 11;
+        y ^= (y << 7) & 0x9d2c5680;
+        y ^= (y << 15) & 0xefc60000;
+        y ^= y >> 18;
+        y as u64
+    }
+}
+
+pub struct Mt19937_64 {
+    state: [u64; 312],
+    index: usize,
+}
+
+impl Mt19937_64 {
+    pub fn new(seed: u64) -> Self {
+        let mut state = [0; 312];
+        state[0] = seed;
+        for i in 1..312 {
+            state[i] = 6364136223846793005u64
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 62))
+                .wrapping_add(i as u64);
+        }
+        Self { state, index: 312 }
+    }
+    fn twist(&mut self) {
+        for i in 0..312 {
+            let y = (self.state[i] & 0xffffffff80000000) | (self.state[(i + 1) % 312] & 0x7fffffff);
+            let mut next = self.state[(i + 156) % 312] ^ (y >> 1);
+            if y & 1 == 1 {
+                next ^= 0xb5026f5aa96619e9;
+            }
+            self.state[i] = next;
+        }
+        self.index = 0;
+    }
+}
+
+impl Engine for Mt19937_64 {
+    const MAX: u64 = u64::MAX;
+    fn next(&mut self) -> u64 {
+        if self.index >= 312 {
+            self.twist();
+        }
+        let mut y = self.state[self.index];
+        self.index += 1;
+        y ^= (y >> 29) & 0x5555555555555555;
+        y ^= (y << 17) & 0x71d67fffeda60000;
+        y ^= (y << 37) & 0xfff7eee000000000;
+        y ^= y >> 43;
+        y
+    }
+}
+
+fn uniform_int_distribution<E: Engine>(engine: &mut E, lo: u64, hi: u64) -> u64 {
+    let urange = hi - lo;
+    let result = if E::MAX > urange {
+        let uerange = urange + 1;
+        if E::MAX == u32::MAX as u64 {
+            let mut product = engine.next() * uerange;
+            if (product as u32 as u64) < uerange {
+                let threshold = (uerange as u32).wrapping_neg() as u64 % uerange;
+                while (product as u32 as u64) < threshold {
+                    product = engine.next() * uerange;
+                }
+            }
+            product >> 32
+        } else if E::MAX == u64::MAX {
+            let mut product = engine.next() as u128 * uerange as u128;
+            if (product as u64) < uerange {
+                let threshold = uerange.wrapping_neg() % uerange;
+                while (product as u64) < threshold {
+                    product = engine.next() as u128 * uerange as u128;
+                }
+            }
+            (product >> 64) as u64
+        } else {
+            let scaling = E::MAX / uerange;
+            let past = uerange * scaling;
+            loop {
+                let result = engine.next();
+                if result < past {
+                    break result / scaling;
+                }
+            }
+        }
+    } else if E::MAX < urange {
+        let uerngrange = E::MAX.wrapping_add(1);
+        loop {
+            let high = uniform_int_distribution(engine, 0, urange / uerngrange);
+            let tmp = uerngrange.wrapping_mul(high);
+            let result = tmp.wrapping_add(engine.next());
+            if result <= urange && result >= tmp {
+                break result;
+            }
+        }
+    } else {
+        engine.next()
+    };
+    lo + result
+}
+
+pub struct Rand {
+    r: Vec<u32>,
+}
+
+impl Rand {
+    pub fn new(seed: u32) -> Self {
+        let mut r = vec![if seed == 0 { 1 } else { seed }];
+        for i in 1..31 {
+            let word = 16807 * r[i - 1] as i32 as i64 % 2147483647;
+            r.push(if word < 0 { word + 2147483647 } else { word } as u32);
+        }
+        for i in 31..34 {
+            r.push(r[i - 31]);
+        }
+        let mut rand = Self { r };
+        for _ in 34..344 {
+            rand.step();
+        }
+        rand
+    }
+    fn step(&mut self) -> u32 {
+        let n = self.r.len();
+        let next = self.r[n - 31].wrapping_add(self.r[n - 3]);
+        self.r.push(next);
+        self.r.remove(0);
+        next >> 1
+    }
+}
+
+impl Engine for Rand {
+    const MAX: u64 = i32::MAX as u64;
+    fn next(&mut self) -> u64 {
+        self.step() as u64
+    }
+}
+
+pub struct JavaRandom {
+    seed: u64,
+}
+
+impl JavaRandom {
+    const MULTIPLIER: u64 = 0x5DEECE66D;
+    const MASK: u64 = (1 << 48) - 1;
+    pub fn new(seed: i64) -> Self {
+        Self {
+            seed: (seed as u64 ^ Self::MULTIPLIER) & Self::MASK,
+        }
+    }
+    fn bits(&mut self, bits: u32) -> i32 {
+        self.seed = (self.seed.wrapping_mul(Self::MULTIPLIER).wrapping_add(0xB)) & Self::MASK;
+        (self.seed >> (48 - bits)) as i32
+    }
+    fn next_int(&mut self, bound: i32) -> i32 {
+        if bound & -bound == bound {
+            return ((bound as i64 * self.bits(31) as i64) >> 31) as i32;
+        }
+        loop {
+            let bits = self.bits(31);
+            let val = bits % bound;
+            if bits.wrapping_sub(val).wrapping_add(bound - 1) >= 0 {
+                return val;
+            }
+        }
+    }
+    fn next_long(&mut self) -> i64 {
+        ((self.bits(32) as i64) << 32).wrapping_add(self.bits(32) as i64)
+    }
+}
+
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl Engine for SplitMix64 {
+    const MAX: u64 = u64::MAX;
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Generator {
+    Mt19937,
+    Mt19937_64,
+    Rand,
+    JavaRandom,
+    SplitMix64,
+}
+
+impl FromStr for Generator {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mt19937" => Ok(Self::Mt19937),
+            "mt19937_64" => Ok(Self::Mt19937_64),
+            "rand" => Ok(Self::Rand),
+            "java" => Ok(Self::JavaRandom),
+            "splitmix64" => Ok(Self::SplitMix64),
+            _ => Err(()),
+        }
+    }
+}
+
+pub fn bases(generator: Generator, seed: u64, lo: u64, hi: u64, count: usize) -> Vec<BigInt> {
+    let range = (hi - lo).checked_add(1);
+    let result: Vec<u64> = match generator {
+        Generator::Mt19937 => {
+            let mut engine = Mt19937::new(seed as u32);
+            (0..count)
+                .map(|_| uniform_int_distribution(&mut engine, lo, hi))
+                .collect()
+        }
+        Generator::Mt19937_64 => {
+            let mut engine = Mt19937_64::new(seed);
+            (0..count)
+                .map(|_| uniform_int_distribution(&mut engine, lo, hi))
+                .collect()
+        }
+        Generator::Rand => {
+            let mut engine = Rand::new(seed as u32);
+            (0..count)
+                .map(|_| match range {
+                    Some(range) => lo + engine.next() % range,
+                    None => engine.next(),
+                })
+                .collect()
+        }
+        Generator::JavaRandom => {
+            let mut engine = JavaRandom::new(seed as i64);
+            (0..count)
+                .map(|_| match range.map(i32::try_from) {
+                    Some(Ok(bound)) => lo + engine.next_int(bound) as u64,
+                    Some(Err(_)) => {
+                        lo + (engine.next_long() as i128).rem_euclid(range.unwrap() as i128) as u64
+                    }
+                    None => engine.next_long() as u64,
+                })
+                .collect()
+        }
+        Generator::SplitMix64 => {
+            let mut engine = SplitMix64::new(seed);
+            (0..count)
+                .map(|_| match range {
+                    Some(range) => lo + engine.next() % range,
+                    None => engine.next(),
+                })
+                .collect()
+        }
+    };
+    result
+        .into_iter()
+        .map(|val| BigInt::from_u64(val).unwrap())
+        .collect()
+}
+
+pub fn candidates(
+    generator: Generator,
+    seeds: RangeInclusive<u64>,
+    lo: u64,
+    hi: u64,
+    modulo: &[BigInt],
+) -> (Vec<BigInt>, Vec<BigInt>) {
+    let mut seen = HashSet::new();
+    let mut pairs = Vec::new();
+    for seed in seeds {
+        for pair in modulo
+            .iter()
+            .cloned()
+            .zip(bases(generator, seed, lo, hi, modulo.len()))
+        {
+            if seen.insert(pair.clone()) {
+                pairs.push(pair);
+            }
+        }
+    }
+    pairs.into_iter().unzip()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mt19937_reference() {
+        let mut engine = Mt19937::new(5489);
+        let value = (0..10000).map(|_| engine.next()).last();
+        assert_eq!(value, Some(4123659995));
+    }
+
+    #[test]
+    fn mt19937_64_reference() {
+        let mut engine = Mt19937_64::new(5489);
+        let value = (0..10000).map(|_| engine.next()).last();
+        assert_eq!(value, Some(9981545732273789042));
+    }
+
+    #[test]
+    fn uniform_int_distribution_reference() {
+        let mut engine = Mt19937::new(5489);
+        let values = (0..10)
+            .map(|_| uniform_int_distribution(&mut engine, 1, 6))
+            .collect::<Vec<_>>();
+        assert_eq!(values, [5, 1, 6, 6, 1, 6, 6, 2, 4, 2]);
+    }
+
+    #[test]
+    fn rand_reference() {
+        let mut engine = Rand::new(1);
+        let values = (0..3).map(|_| engine.next()).collect::<Vec<_>>();
+        assert_eq!(values, [1804289383, 846930886, 1681692777]);
+    }
+
+    #[test]
+    fn java_random_reference() {
+        let mut engine = JavaRandom::new(42);
+        let values = (0..5).map(|_| engine.next_int(10)).collect::<Vec<_>>();
+        assert_eq!(values, [0, 3, 8, 4, 0]);
+    }
+
+    #[test]
+    fn splitmix64_reference() {
+        assert_eq!(SplitMix64::new(0).next(), 16294208416658607535);
+    }
+
+    #[test]
+    fn uniform_int_distribution_small_engine() {
+        let mut engine = Rand::new(1);
+        let values = (0..3)
+            .map(|_| uniform_int_distribution(&mut engine, 1, 6))
+            .collect::<Vec<_>>();
+        assert_eq!(values, [6, 3, 5]);
+    }
+
+    #[test]
+    fn full_range_bases() {
+        let full = |generator: &str, seed, count| {
+            bases(generator.parse().unwrap(), seed, 0, u64::MAX, count)
+                .into_iter()
+                .map(|val| u64::try_from(val).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(full("mt19937", 5489, 1), [15028999435905310454]);
+        assert_eq!(full("mt19937_64", 5489, 1), [14514284786278117030]);
+        assert_eq!(full("rand", 1, 3), [1804289383, 846930886, 1681692777]);
+        assert_eq!(full("java", 42, 1), [13421181215734401783]);
+        assert_eq!(full("splitmix64", 0, 1), [16294208416658607535]);
+    }
+}
@@ -0,0 +1,361 @@
+use bigdecimal::{num_bigint::BigInt, FromPrimitive, One, Zero};
+
+const SMALL: u64 = 1 << 16;
+pub const LIMIT: usize = 1000;
+
+fn normalize(x: &BigInt, modulo: &BigInt) -> BigInt {
+    (x % modulo + modulo) % modulo
+}
+
+fn eval(poly: &[BigInt], x: &BigInt, modulo: &BigInt) -> BigInt {
+    poly.iter()
+        .rev()
+        .fold(BigInt::ZERO, |acc, c| normalize(&(acc * x + c), modulo))
+}
+
+fn derivative(poly: &[BigInt]) -> Vec<BigInt> {
+    poly.iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, c)| c * i)
+        .collect()
+}
+
+fn trim(mut poly: Vec<BigInt>) -> Vec<BigInt> {
+    while poly.last().is_some_and(|c| c.is_zero()) {
+        poly.pop();
+    }
+    poly
+}
+
+fn poly_rem(a: &[BigInt], b: &[BigInt], p: &BigInt) -> (Vec<BigInt>, Vec<BigInt>) {
+    let mut r = a.iter().map(|c| normalize(c, p)).collect::<Vec<_>>();
+    let inv = b.last().unwrap().modinv(p).unwrap();
+    let mut q = vec![BigInt::ZERO; a.len().saturating_sub(b.len()) + 1];
+    while r.len() >= b.len() {
+        let shift = r.len() - b.len();
+        let factor = normalize(&(r.last().unwrap() * &inv), p);
+        for (i, c) in b.iter().enumerate() {
+            r[shift + i] = normalize(&(&r[shift + i] - &factor * c), p);
+        }
+        q[shift] = factor;
+        r = trim(r);
+    }
+    (trim(q), r)
+}
+
+fn poly_gcd(a: Vec<BigInt>, b: Vec<BigInt>, p: &BigInt) -> Vec<BigInt> {
+    let (mut a, mut b) = (trim(a), trim(b));
+    while !b.is_empty() {
+        let r = poly_rem(&a, &b, p).1;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+fn poly_mulmod(a: &[BigInt], b: &[BigInt], m: &[BigInt], p: &BigInt) -> Vec<BigInt> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut c = vec![BigInt::ZERO; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            c[i + j] += x * y;
+        }
+    }
+    poly_rem(&c, m, p).1
+}
+
+fn poly_powmod(a: &[BigInt], e: &BigInt, m: &[BigInt], p: &BigInt) -> Vec<BigInt> {
+    let mut result = vec![BigInt::one()];
+    for i in (0..e.bits()).rev() {
+        result = poly_mulmod(&result, &result, m, p);
+        if e.bit(i) {
+            result = poly_mulmod(&result, a, m, p);
+        }
+    }
+    result
+}
+
+fn sub(a: &[BigInt], b: &[BigInt], p: &BigInt) -> Vec<BigInt> {
+    let n = a.len().max(b.len());
+    let zero = BigInt::ZERO;
+    trim(
+        (0..n)
+            .map(|i| normalize(&(a.get(i).unwrap_or(&zero) - b.get(i).unwrap_or(&zero)), p))
+            .collect(),
+    )
+}
+
+fn split(poly: Vec<BigInt>, p: &BigInt, roots: &mut Vec<BigInt>) {
+    if poly.len() <= 1 {
+        return;
+    }
+    if poly.len() == 2 {
+        let inv = poly[1].modinv(p).unwrap();
+        roots.push(normalize(&(-&poly[0] * inv), p));
+        return;
+    }
+    let e: BigInt = (p - 1) / 2u32;
+    let mut a = BigInt::one();
+    loop {
+        let w = poly_powmod(&[a.clone(), BigInt::one()], &e, &poly, p);
+        let d = poly_gcd(sub(&w, &[BigInt::one()], p), poly.clone(), p);
+        if d.len() > 1 && d.len() < poly.len() {
+            let q = poly_rem(&poly, &d, p).0;
+            split(d, p, roots);
+            split(q, p, roots);
+            return;
+        }
+        a += 1;
+    }
+}
+
+fn prime_roots(polys: &[Vec<BigInt>], p: &BigInt) -> Option<Vec<BigInt>> {
+    if p <= &BigInt::from_u64(SMALL).unwrap() {
+        let mut x = BigInt::ZERO;
+        let mut roots = Vec::new();
+        while &x < p {
+            if polys.iter().all(|poly| eval(poly, &x, p).is_zero()) {
+                roots.push(x.clone());
+            }
+            x += 1;
+        }
+        return Some(roots);
+    }
+    let g = polys.iter().fold(Vec::new(), |g, poly| {
+        poly_gcd(g, poly.iter().map(|c| normalize(c, p)).collect(), p)
+    });
+    // Samples vanishing modulo p admit every residue as a root.
+    if g.is_empty() {
+        return None;
+    }
+    if g.len() == 1 {
+        return Some(Vec::new());
+    }
+    let x = vec![BigInt::ZERO, BigInt::one()];
+    let h = poly_powmod(&x, p, &g, p);
+    let mut roots = Vec::new();
+    split(poly_gcd(sub(&h, &x, p), g, p), p, &mut roots);
+    Some(roots)
+}
+
+fn lift(polys: &[Vec<BigInt>], p: &BigInt, exponent: u32) -> Option<Vec<BigInt>> {
+    let mut roots = prime_roots(polys, p)?;
+    let mut q = p.clone();
+    for _ in 1..exponent {
+        let next = &q * p;
+        let mut lifted = Vec::new();
+        for r in &roots {
+            let steps = if p <= &BigInt::from_u64(SMALL).unwrap() {
+                let mut steps = Vec::new();
+                let mut t = BigInt::ZERO;
+                while &t < p {
+                    steps.push(t.clone());
+                    t += 1;
+                }
+                steps
+            } else {
+                let step = polys.iter().find_map(|poly| {
+                    let inv = eval(&derivative(poly), r, p).modinv(p)?;
+                    Some(normalize(&(-eval(poly, r, &next) / &q * inv), p))
+                });
+                // With every derivative vanishing at r, either all p lifts of
+                // r are roots or none are.
+                if step.is_none() && polys.iter().all(|poly| eval(poly, r, &next).is_zero()) {
+                    return None;
+                }
+                step.into_iter().collect()
+            };
+            for t in steps {
+                let x = r + t * &q;
+                if polys.iter().all(|poly| eval(poly, &x, &next).is_zero()) {
+                    lifted.push(x);
+                }
+            }
+        }
+        if lifted.len() > LIMIT {
+            return None;
+        }
+        roots = lifted;
+        q = next;
+    }
+    Some(roots)
+}
+
+fn is_prime(n: &BigInt) -> bool {
+    if n < &BigInt::from(2) {
+        return false;
+    }
+    let small = [2u32, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+    for &a in &small {
+        if (n % a).is_zero() {
+            return n == &BigInt::from(a);
+        }
+    }
+    let m: BigInt = n - 1;
+    let s = m.trailing_zeros().unwrap();
+    let d = &m >> s;
+    small.iter().all(|&a| {
+        let mut x = BigInt::from(a).modpow(&d, n);
+        if x.is_one() || x == m {
+            return true;
+        }
+        for _ in 1..s {
+            x = &x * &x % n;
+            if x == m {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+fn gcd(a: &BigInt, b: &BigInt) -> BigInt {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    while !b.is_zero() {
+        let r = &a % &b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+fn rho(n: &BigInt) -> Option<BigInt> {
+    for c in 1..100u32 {
+        let f = |x: &BigInt| (x * x + c) % n;
+        let (mut x, mut y) = (BigInt::from(2), BigInt::from(2));
+        for _ in 0..1 << 20 {
+            x = f(&x);
+            y = f(&f(&y));
+            let d = gcd(&normalize(&(&x - &y), n), n);
+            if d == *n {
+                break;
+            }
+            if !d.is_one() {
+                return Some(d);
+            }
+        }
+    }
+    None
+}
+
+fn factor(n: &BigInt, factors: &mut Vec<BigInt>) {
+    if n.is_one() {
+        return;
+    }
+    if is_prime(n) {
+        factors.push(n.clone());
+        return;
+    }
+    let mut p = BigInt::from(2);
+    while &p * &p <= *n && p < BigInt::from_u64(SMALL).unwrap() {
+        if (n % &p).is_zero() {
+            factors.push(p.clone());
+            return factor(&(n / p), factors);
+        }
+        p += 1;
+    }
+    match rho(n) {
+        Some(d) => {
+            factor(&d, factors);
+            factor(&(n / d), factors);
+        }
+        None => factors.push(n.clone()),
+    }
+}
+
+pub fn recover_bases(modulo: &BigInt, samples: &[(String, BigInt)]) -> Option<Vec<BigInt>> {
+    let polys = samples
+        .iter()
+        .map(|(s, h)| {
+            let mut poly = s
+                .chars()
+                .map(|c| BigInt::from_u32(c as u32).unwrap())
+                .collect::<Vec<_>>();
+            if poly.is_empty() {
+                poly.push(BigInt::ZERO);
+            }
+            poly[0] -= h;
+            poly
+        })
+        .collect::<Vec<_>>();
+    let mut factors = Vec::new();
+    factor(modulo, &mut factors);
+    factors.sort();
+    let mut result = vec![BigInt::ZERO];
+    let mut product = BigInt::one();
+    let mut i = 0;
+    while i < factors.len() {
+        let p = factors[i].clone();
+        let mut exponent = 0;
+        while i < factors.len() && factors[i] == p {
+            exponent += 1;
+            i += 1;
+        }
+        let q = p.pow(exponent);
+        let roots = lift(&polys, &p, exponent)?;
+        let inv = product.modinv(&q).unwrap();
+        let mut combined = Vec::new();
+        for x in &result {
+            for r in &roots {
+                let t = normalize(&((r - x) * &inv), &q);
+                combined.push(x + t * &product);
+            }
+        }
+        if combined.len() > LIMIT {
+            return None;
+        }
+        result = combined;
+        product *= q;
+    }
+    result.retain(|x| polys.iter().all(|poly| eval(poly, x, modulo).is_zero()));
+    result.sort();
+    result.dedup();
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recovers_base() {
+        let modulo = BigInt::from(1000000007);
+        let base = BigInt::from(131);
+        let samples = ["ab", "xyz"]
+            .iter()
+            .map(|s| {
+                let hash = s.chars().rev().fold(BigInt::ZERO, |acc, c| {
+                    (acc * &base + BigInt::from_u32(c as u32).unwrap()) % &modulo
+                });
+                (s.to_string(), hash)
+            })
+            .collect::<Vec<_>>();
+        assert!(recover_bases(&modulo, &samples).unwrap().contains(&base));
+    }
+
+    #[test]
+    fn reports_too_many_bases() {
+        let modulo = BigInt::from(65521u64 * 65519);
+        let samples = [("a".to_string(), BigInt::from(97))];
+        assert_eq!(recover_bases(&modulo, &samples), None);
+    }
+
+    #[test]
+    fn reports_unconstrained_large_prime() {
+        let modulo = BigInt::from(1000000007);
+        let samples = [("a".to_string(), BigInt::from(97))];
+        assert_eq!(recover_bases(&modulo, &samples), None);
+    }
+
+    #[test]
+    fn reports_singular_lift() {
+        // x^2 + 98x + 2401 = (x + 49)^2 has a double root modulo 65537.
+        let modulo = BigInt::from(65537u64 * 65537);
+        let hash = normalize(&BigInt::from(97 - 2401), &modulo);
+        let samples = [("ab\u{1}".to_string(), hash)];
+        assert_eq!(recover_bases(&modulo, &samples), None);
+    }
+}